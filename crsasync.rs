@@ -0,0 +1,204 @@
+//
+//                          c r s a s y n c . r s
+//
+// Summary:
+//    A minimal thread hand-off, as an async runtime makes, for timing in Rust.
+//
+// Introduction:
+//    Some programs run CPU-bound work like csub() from async code, handing each
+//    call to a pool of threads set aside for blocking work - in tokio, using
+//    tokio::task::spawn_blocking() - and awaiting the result. This module provides
+//    just enough of that to measure what it costs: a thread that runs the jobs
+//    handed to it, a handle for each job that is a Future and is completed by the
+//    thread when the job is done, and a block_on() that polls a Future to
+//    completion on the calling thread, sleeping until it is woken. These are the
+//    steps a real runtime goes through for each such call, without its other
+//    machinery, so what it times is the hand-off itself, not the overhead of
+//    tokio or any other real runtime.
+//
+// This version:
+//    This version uses only the standard library, since these programs are
+//    built with plain rustc and can't use tokio; see the notes at the end. It is
+//    only compiled into crsmain.rs with the 'async' feature.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Described as a thread hand-off, not as an async runtime.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{mpsc,Arc,Mutex};
+use std::task::{Context,Poll,Wake,Waker};
+use std::thread;
+
+//  A job for the blocking thread.
+
+type Job = Box<dyn FnOnce() + Send>;
+
+//  A BlockingPool is the thread that runs the jobs passed to spawn_blocking(),
+//  one at a time, in the order they are passed. Dropping it lets the thread
+//  finish whatever jobs it has and end, and waits for it.
+
+pub struct BlockingPool {
+   sender: Option<mpsc::Sender<Job>>,
+   thread: Option<thread::JoinHandle<()>>,
+}
+
+//  What a job and its JoinHandle share: the result, once there is one, and
+//  the Waker to call when it arrives, if the handle has been polled already.
+
+struct Shared<R> {
+   result: Option<R>,
+   waker: Option<Waker>,
+}
+
+//  A JoinHandle is returned by spawn_blocking(), and is a Future that becomes
+//  ready with the job's result.
+
+pub struct JoinHandle<R> {
+   shared: Arc<Mutex<Shared<R>>>,
+}
+
+impl BlockingPool {
+   pub fn new () -> BlockingPool {
+      let (sender,receiver) = mpsc::channel::<Job>();
+      let thread = thread::spawn(move || {
+         for job in receiver {
+            job();
+         }
+      });
+      BlockingPool { sender: Some(sender), thread: Some(thread) }
+   }
+
+   //  Passes a job to the blocking thread, returning a handle that can be
+   //  waited on for its result.
+
+   pub fn spawn_blocking<F,R> (&self,job: F) -> JoinHandle<R>
+                           where F: FnOnce() -> R + Send + 'static, R: Send + 'static {
+      let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+      let job_shared = shared.clone();
+      let job: Job = Box::new(move || {
+         let result = job();
+         let mut shared = job_shared.lock().unwrap();
+         shared.result = Some(result);
+         if let Some(waker) = shared.waker.take() {
+            waker.wake();
+         }
+      });
+      if let Some(sender) = &self.sender {
+         sender.send(job).expect("blocking thread has ended");
+      }
+      JoinHandle { shared }
+   }
+}
+
+impl Drop for BlockingPool {
+   fn drop (&mut self) {
+      self.sender = None;
+      if let Some(thread) = self.thread.take() {
+         let _ = thread.join();
+      }
+   }
+}
+
+impl<R> Future for JoinHandle<R> {
+   type Output = R;
+   fn poll (self: Pin<&mut Self>,context: &mut Context) -> Poll<R> {
+      let mut shared = self.shared.lock().unwrap();
+      match shared.result.take() {
+         Some(result) => Poll::Ready(result),
+         None => {
+            shared.waker = Some(context.waker().clone());
+            Poll::Pending
+         }
+      }
+   }
+}
+
+//  The Waker used by block_on(), which wakes the thread that is waiting.
+
+struct ThreadWaker {
+   thread: thread::Thread,
+}
+
+impl Wake for ThreadWaker {
+   fn wake (self: Arc<Self>) {
+      self.thread.unpark();
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             B l o c k  O n
+//
+//   Polls a Future on the calling thread until it is ready, parking the
+//   thread in between, and returns its value.
+
+pub fn block_on<F: Future> (future: F) -> F::Output {
+   let mut future = Box::pin(future);
+   let waker = Waker::from(Arc::new(ThreadWaker { thread: thread::current() }));
+   let mut context = Context::from_waker(&waker);
+   loop {
+      match future.as_mut().poll(&mut context) {
+         Poll::Ready(value) => return value,
+         Poll::Pending => thread::park(),
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o This was asked for as an optional feature using tokio, with the csub()
+     calls made through tokio::task::spawn_blocking(). These programs are
+     built with plain rustc, which can't use external crates like tokio, so
+     this module does the same steps itself. Each call is handed to another
+     thread, which runs it and wakes the waiting thread, which then picks up
+     the result - two cross-thread wakeups per call, which any runtime doing
+     this has to make. A real runtime adds its own bookkeeping on top, and
+     normally keeps a pool of blocking threads rather than just the one, so
+     the times measured here are better seen as a lower limit.
+
+   o The programs are built with the 2015 edition, the rustc default, in
+     which async and await aren't keywords, so there is no async code here.
+     An async function that awaited each call in turn, inside block_on(),
+     would poll each JoinHandle in the same way block_on() does when passed
+     the handle directly, which is what crsmain.rs does.
+
+   o thread::park() can return without unpark() having been called, so
+     block_on() just polls again in that case. A JoinHandle stores the
+     Waker before checking for the result under the same lock that the job
+     holds while storing it, so a wakeup can't be missed.
+
+   o On the test machine, with rustc 1.95, crsmain --async measured about 5
+     microseconds extra per call. For the default 2000 by 10 arrays, where a
+     call takes about 20 microseconds, that made the handed-off calls about
+     28% slower overall. For 1000 by 1000 arrays, where a call takes about a
+     millisecond, the difference was 0.3%, and for arrays of 10 elements the
+     handed-off calls took 400 times as long. Wrapping a CPU-bound kernel like
+     csub() this way only makes sense if each call does a good deal of work.
+
+*/
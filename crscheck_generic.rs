@@ -0,0 +1,204 @@
+//
+//                  c r s c h e c k _ g e n e r i c . r s
+//
+// Summary:
+//    Cross-check of the generic Rust 2D array access subroutine for f32, f64 and i32.
+//
+// Introduction:
+//    This is a check program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines all set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    The version in crssub_generic.rs does this for any element type that
+//    implements its Element trait, and the compiler generates a separate copy
+//    of it for each type. This program checks that those copies all agree: it
+//    runs csub() for f32, f64 and i32 on the same whole-number input values,
+//    which all three types can hold exactly, and checks that the f32 and f64
+//    results are numerically equal, that the i32 results are the f64 results
+//    truncated to whole numbers (which, for whole numbers, changes nothing),
+//    and that the checksums of the three sets of results agree. A change to the
+//    trait, or to one of its implementations, that made one type behave
+//    differently would show up here.
+//
+// This version:
+//    The fixed and random array shapes are the same as those used by
+//    crscheck_unsafe.rs. The values involved are all well below 2^24, so they
+//    are exact in an f32, and any difference at all counts as a failure.
+//
+// Building:
+//    rustc -O crscheck_generic.rs
+//
+// Invocation:
+//    ./crscheck_generic [ntests [seed]]
+//
+//    where:
+//      ntests  is the number of randomly sized arrays tested - default 100.
+//      seed    is the seed for the random sizes - default 1. The same seed
+//              always gives the same sizes, so a failure can be repeated.
+//
+//    The program prints a line for each failure, and a summary at the end.
+//    It exits with a status of 1 if any test failed.
+//
+//    It has to be run by hand. The fixed shapes are also checked by the doc
+//    example for crssub_generic::csub(), which is run with the rest of the
+//    doctests by rustdoc --test crslib.rs.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. The fixed shapes are also a doc example in crssub_generic.rs.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+
+mod crssub_generic;
+mod crsrandom;
+
+use crssub_generic::Element;
+
+//  The fixed shapes tested, as (nx,ny) pairs, before the random ones.
+
+const SHAPES: [(usize,usize); 10] = [
+   (1,1),(2,1),(1,2),(1,1000),(1000,1),(3,5),(5,3),(7,13),(17,1),(2000,10)];
+
+//  The largest number of columns and rows for the randomly sized arrays.
+
+const MAX_NX: usize = 300;
+const MAX_NY: usize = 300;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+   let mut ntests = 100;
+   let mut seed: u64 = 1;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => ntests = number,
+         Err(_error) => println!("Number of tests invalid, using {}",ntests),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<u64>() {
+            Ok(number) => seed = number,
+            Err(_error) => println!("Seed invalid, using {}",seed),
+         };
+      }
+   }
+
+   let mut failures = 0;
+   for (nx,ny) in SHAPES.iter() {
+      if !cross_check(*nx,*ny) {
+         failures += 1;
+      }
+   }
+   let mut random = crsrandom::Random::new(seed);
+   for _itest in 0..ntests {
+      let nx = 1 + (random.next_u64() % MAX_NX as u64) as usize;
+      let ny = 1 + (random.next_u64() % MAX_NY as u64) as usize;
+      if !cross_check(nx,ny) {
+         failures += 1;
+      }
+   }
+   let total = SHAPES.len() + ntests;
+   println!("{} tests, {} failed",total,failures);
+   if failures > 0 {
+      process::exit(1);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             R u n  C s u b
+//
+//   Sets up an input array of the given element type, with ny rows of nx
+//   elements holding the usual descending whole-number values, runs the
+//   generic csub() on it, and returns the output array.
+
+fn run_csub<T: Element> (nx: usize,ny: usize) -> Vec<Vec<T>> {
+   let mut in_array = vec![vec![T::from_index(0); nx]; ny];
+   for (iy,row) in in_array.iter_mut().enumerate() {
+      for (ix,value) in row.iter_mut().enumerate() {
+         *value = T::from_index(nx - ix + ny - iy);
+      }
+   }
+   let mut out_array = vec![vec![T::from_index(0); nx]; ny];
+   crssub_generic::csub(&in_array,nx,ny,&mut out_array);
+   out_array
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           C r o s s  C h e c k
+//
+//   Runs the f32, f64 and i32 versions of csub() for an array with ny rows
+//   of nx elements, and checks that every element agrees and matches the
+//   expected value, and that the checksums - the sums of all the output
+//   elements, accumulated as f64 - agree. Returns true if all is well.
+
+fn cross_check (nx: usize,ny: usize) -> bool {
+   let f32_out = run_csub::<f32>(nx,ny);
+   let f64_out = run_csub::<f64>(nx,ny);
+   let i32_out = run_csub::<i32>(nx,ny);
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = (nx + ny) as f64;
+         let (f32_value,f64_value,i32_value) =
+                               (f32_out[iy][ix],f64_out[iy][ix],i32_out[iy][ix]);
+         if f32_value as f64 != f64_value || i32_value != f64_value.trunc() as i32
+                                                       || f64_value != expected {
+            println!("Failed for {} rows of {} columns: element [{}][{}] is {} as \
+                      f32, {} as f64, {} as i32, expected {}",ny,nx,iy,ix,
+                                       f32_value,f64_value,i32_value,expected);
+            return false;
+         }
+      }
+   }
+   let f32_sum: f64 = f32_out.iter().flatten().map(|value| *value as f64).sum();
+   let f64_sum: f64 = f64_out.iter().flatten().sum();
+   let i32_sum: f64 = i32_out.iter().flatten().map(|value| *value as f64).sum();
+   if f32_sum != f64_sum || i32_sum != f64_sum {
+      println!("Failed for {} rows of {} columns: checksums {} as f32, {} as f64, \
+                {} as i32",ny,nx,f32_sum,f64_sum,i32_sum);
+      return false;
+   }
+   true
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Every output element should be in[iy][ix] + ix + iy, which with the
+     usual input values is nx + ny for every element, so the expected value
+     doesn't depend on the element type at all.
+
+   o The checksums are added up in f64 for all three types, in the same
+     order. Each partial sum is a whole number well below 2^53, so all
+     three sums are exact, and should be identical rather than just close.
+     u8 isn't included, since from_index() takes the index modulo 128 for
+     u8, so its results are deliberately different.
+
+*/
@@ -0,0 +1,178 @@
+//
+//                   c r s c h e c k _ u n s a f e . r s
+//
+// Summary:
+//    Cross-check of the unsafe Rust 2D array access subroutine against crssub.rs.
+//
+// Introduction:
+//    This is a check program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines all set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    The version in crssub_unsafe.rs does this without any bounds checking, so
+//    if it ever went wrong it could quietly overwrite memory it doesn't own.
+//    This program checks that it gives exactly the same results as the
+//    straightforward version in crssub.rs, for a set of fixed array shapes
+//    chosen to be awkward - including a single element, a single row and a
+//    single column - and then for a number of randomly sized arrays.
+//
+// This version:
+//    For every test the arrays are allocated with exactly ny rows of exactly nx
+//    elements, so the vector lengths are equal to the dimensions passed. That
+//    is the boundary case for crssub_unsafe.rs, which is only sound if nx and ny
+//    are no larger than the vector lengths, so if it ever read or wrote past the
+//    end of a row it would do so here.
+//
+// Building:
+//    rustc -O crscheck_unsafe.rs
+//
+//    It is worth also building this without -O, and running it under a
+//    memory checker such as valgrind.
+//
+// Invocation:
+//    ./crscheck_unsafe [ntests [seed]]
+//
+//    where:
+//      ntests  is the number of randomly sized arrays tested - default 100.
+//      seed    is the seed for the random sizes - default 1. The same seed
+//              always gives the same sizes, so a failure can be repeated.
+//
+//    The program prints a line for each failure, and a summary at the end.
+//    It exits with status 0 if all the tests pass, and 1 if any fail.
+//
+//    It has to be run by hand. The fixed shapes are also checked by the doc
+//    examples for crssub_unsafe::csub(), which are run with the rest of the
+//    doctests by rustdoc --test crslib.rs; this program adds the randomly
+//    sized arrays, and a build that can be run under a memory checker.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Now uses the generator in crsrandom.rs for the sizes.
+//    14th Oct 2026. The fixed shapes are also doc examples in crssub_unsafe.rs.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+
+mod crssub;
+mod crssub_unsafe;
+mod crserror;
+mod crsrandom;
+
+//  The fixed shapes tested, as (nx,ny) pairs, before the random ones.
+
+const SHAPES: [(usize,usize); 10] = [
+   (1,1),(2,1),(1,2),(1,1000),(1000,1),(3,5),(5,3),(7,13),(17,1),(2000,10)];
+
+//  The largest number of columns and rows for the randomly sized arrays.
+
+const MAX_NX: usize = 300;
+const MAX_NY: usize = 300;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+   let mut ntests = 100;
+   let mut seed: u64 = 1;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => ntests = number,
+         Err(_error) => println!("Number of tests invalid, using {}",ntests),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<u64>() {
+            Ok(number) => seed = number,
+            Err(_error) => println!("Seed invalid, using {}",seed),
+         };
+      }
+   }
+
+   let mut failures = 0;
+   for (nx,ny) in SHAPES.iter() {
+      if !cross_check(*nx,*ny) {
+         failures += 1;
+      }
+   }
+   let mut random = crsrandom::Random::new(seed);
+   for _itest in 0..ntests {
+      let nx = 1 + (random.next_u64() % MAX_NX as u64) as usize;
+      let ny = 1 + (random.next_u64() % MAX_NY as u64) as usize;
+      if !cross_check(nx,ny) {
+         failures += 1;
+      }
+   }
+   let total = SHAPES.len() + ntests;
+   println!("{} tests, {} failed",total,failures);
+   if failures > 0 {
+      process::exit(1);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           C r o s s  C h e c k
+//
+//   Sets up an input array with ny rows of exactly nx elements, runs both
+//   versions of csub() on it, and checks the outputs are identical and also
+//   match the expected values. The two output arrays start off filled with
+//   different values, so an element that neither version wrote to would
+//   show up as a difference. Returns true if all is well.
+
+fn cross_check (nx: usize,ny: usize) -> bool {
+   let mut in_array = vec![vec![0.0f32; nx]; ny];
+   let mut checked_out = vec![vec![-1.0f32; nx]; ny];
+   let mut unsafe_out = vec![vec![-2.0f32; nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+   crssub::csub(&in_array,nx,ny,&mut checked_out);
+   crssub_unsafe::csub(&in_array,nx,ny,&mut unsafe_out);
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = in_array[iy][ix] + (ix + iy) as f32;
+         if unsafe_out[iy][ix] != checked_out[iy][ix] || checked_out[iy][ix] != expected {
+            println!("Failed for {} rows of {} columns: element [{}][{}] is {}, \
+                      crssub.rs gives {}, expected {}",ny,nx,iy,ix,
+                            unsafe_out[iy][ix],checked_out[iy][ix],expected);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The check can only show that crssub_unsafe.rs behaves correctly when it
+     is passed valid dimensions. Passing an nx or ny larger than the arrays
+     is undefined behaviour, and isn't something a program can test for.
+
+*/
@@ -0,0 +1,167 @@
+//
+//                           c r s d i f f . r s
+//
+// Summary:
+//    Compares two binary array files written by the Rust 2D array access tests.
+//
+// Introduction:
+//    This is a small utility program written as part of a study into how well
+//    different languages handle accessing elements of 2D rectangular arrays.
+//    The test programs can write their output array to a raw binary file (see
+//    the --output option of crsmain.rs), and this program compares two such
+//    files element by element. This makes it easy to confirm that two different
+//    versions of the code, or the same code built with two different compilers
+//    or sets of options, produce the same results.
+//
+// Building:
+//    rustc -O crsdiff.rs
+//
+//    This picks up the file reading code from crsfile.rs.
+//
+// Invocation:
+//    ./crsdiff file1 file2
+//
+//    The program reports the number of elements that differ, the index of
+//    the element with the largest difference, and the largest absolute and
+//    relative differences. It exits with status 0 if the files are identical,
+//    1 if they differ, and 2 if they can't be read or are different sizes.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Differences that are NaN are counted separately.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+
+mod crsfile;
+
+use crsfile::Endian;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   let args: Vec<String> = env::args().collect();
+   if args.len() != 3 {
+      println!("Usage: {} file1 file2",args[0]);
+      process::exit(2);
+   }
+
+   //  Read both files. The comparison only makes sense if they hold the
+   //  same number of values.
+
+   let mut files: Vec<Vec<f32>> = Vec::new();
+   for path in &args[1..] {
+      match crsfile::read_f32_file(path,Endian::Little) {
+         Ok(values) => files.push(values),
+         Err(error) => {
+            println!("Unable to read {}: {}",path,error);
+            process::exit(2);
+         }
+      }
+   }
+   let (first,second) = (&files[0],&files[1]);
+   if first.len() != second.len() {
+      println!("{} has {} values, but {} has {}",
+                            args[1],first.len(),args[2],second.len());
+      process::exit(2);
+   }
+
+   //  Work through the two sets of values. The relative difference is taken
+   //  with respect to the larger of the two magnitudes, which avoids a
+   //  division by zero unless both values are zero, in which case they
+   //  can't differ anyway. A difference that comes out as a NaN, because
+   //  either value is a NaN, has no size to compare, so these are counted
+   //  separately, and the largest differences are only
+   //  for the rest.
+
+   let mut ndiff = 0;
+   let mut nan_diffs = 0;
+   let mut first_nan_index = 0;
+   let mut max_abs = 0.0f64;
+   let mut max_rel = 0.0f64;
+   let mut max_index = None;
+   for (index,(a,b)) in first.iter().zip(second.iter()).enumerate() {
+      if a != b {
+         ndiff += 1;
+         let abs_diff = (*a as f64 - *b as f64).abs();
+         if abs_diff.is_nan() {
+            if nan_diffs == 0 {
+               first_nan_index = index;
+            }
+            nan_diffs += 1;
+            continue;
+         }
+         let rel_diff = abs_diff / (a.abs().max(b.abs()) as f64);
+         if max_index.is_none() || abs_diff > max_abs {
+            max_abs = abs_diff;
+            max_index = Some(index);
+         }
+         if rel_diff > max_rel { max_rel = rel_diff; }
+      }
+   }
+
+   println!("Compared {} values, {} differ",first.len(),ndiff);
+   if nan_diffs > 0 {
+      println!("{} differences are NaN, the first at element {}: {} vs {}",nan_diffs,
+                    first_nan_index,first[first_nan_index],second[first_nan_index]);
+   }
+   if let Some(max_index) = max_index {
+      println!("Largest difference at element {}: {} vs {}",
+                              max_index,first[max_index],second[max_index]);
+      println!("Largest absolute difference {}, largest relative difference {}",
+                                                               max_abs,max_rel);
+   }
+   if ndiff > 0 {
+      process::exit(1);
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A NaN value in either file compares unequal to everything, including
+     itself, so it will always be counted as a difference. That seems the
+     right thing to do for a tool whose job is to spot anything unexpected.
+     But the difference it gives is a NaN, which compares as neither larger
+     nor smaller than anything, so it can't take part in finding the largest
+     difference: it would only be kept if it happened to come first, and a
+     NaN relative difference would never be kept at all. So differences that
+     come out as NaN are counted and reported separately, with the first of
+     them, and the largest absolute and relative differences are for the
+     others. (Two infinities of the same sign compare equal, so can't give
+     a NaN difference; of opposite signs, they differ by infinity.) An
+     infinity against a finite value gives an infinite absolute difference
+     and a relative difference of NaN (infinity over infinity), which isn't
+     counted as the largest relative difference.
+
+   o The files have no header, so all this program can check is the number
+     of values. If the arrays had different shapes but the same number of
+     elements, it will happily compare them anyway.
+
+*/
@@ -0,0 +1,169 @@
+//
+//                          c r s e r r o r . r s
+//
+// Summary:
+//    Error type for the checked versions of the Rust 2D array access routines.
+//
+// Introduction:
+//    The test routines in this study trust their callers to pass arrays of
+//    the right size, and either panic (if the array accesses are checked) or
+//    behave unpredictably (if they aren't) if the arrays are too small. That's
+//    fine for a test program, but if the routines are to be used from other
+//    code it's better for them to report a problem in the normal Rust way, by
+//    returning an error. This module defines the error type used for this by
+//    the checked versions of the routines, such as csub_checked() in crssub.rs
+//    and csub1d_checked() in crssub1d_checked.rs.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added OutOfBounds, for crssub_sparse.rs.
+//    14th Oct 2026. Added TooManyElements.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This module is shared by a number of programs, not all of which use
+//  every routine in it.
+
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+
+/// The problems that the checked routines can detect. The array name is
+/// "input" or "output", so the message can say which array was wrong. The
+/// Display implementation gives a message that can be printed as it is:
+///
+/// ```
+/// use crslib::CsubError;
+/// assert_eq!(CsubError::EmptyArray.to_string(),
+///            "array dimensions are zero, nothing to do");
+/// assert_eq!(CsubError::TooFewRows { array: "input", rows: 2, ny: 3 }.to_string(),
+///            "input array has 2 rows, but 3 are needed");
+/// assert_eq!(CsubError::RowTooShort { array: "output", row: 1, len: 4, nx: 5 }.to_string(),
+///            "row 1 of output array has 4 elements, but 5 are needed");
+/// assert_eq!(CsubError::BufferTooSmall { array: "input", len: 6, needed: 8 }.to_string(),
+///            "input array has 6 elements, but 8 are needed");
+/// assert_eq!(CsubError::OutOfBounds { ix: 7, iy: 2, nx: 5, ny: 3 }.to_string(),
+///            "element (7,2) is outside the 5 by 3 array");
+/// assert_eq!(CsubError::TooManyElements { nx: usize::MAX, ny: 2 }.to_string(),
+///            format!("2 rows of {} elements is more than can be indexed",usize::MAX));
+/// ```
+///
+/// It implements std::error::Error, so a checked call can pass it on with
+/// the ? operator from a function returning a boxed error, and the caller
+/// can get the CsubError back with downcast_ref():
+///
+/// ```
+/// use std::error::Error;
+/// use crslib::CsubError;
+///
+/// fn process (nx: usize,ny: usize) -> Result<(),Box<dyn Error>> {
+///    let input_array = vec![vec![1.0f32; 3]; 2];
+///    let mut output_array = vec![vec![0.0f32; 2]; 2];
+///    crslib::csub_naive_checked (&input_array,nx,ny,&mut output_array)?;
+///    Ok(())
+/// }
+///
+/// let error = process(3,2).unwrap_err();
+/// assert_eq!(error.to_string(),"row 0 of output array has 2 elements, but 3 are needed");
+/// assert_eq!(error.downcast_ref::<CsubError>(),
+///            Some(&CsubError::RowTooShort { array: "output", row: 0, len: 2, nx: 3 }));
+/// assert!(process(2,2).is_ok());
+///
+/// let input_array = vec![1.0f32; 4];
+/// let mut output_array = vec![0.0f32; 4];
+/// assert_eq!(crslib::csub_flat_checked(&input_array,usize::MAX,2,&mut output_array),
+///            Err(CsubError::TooManyElements { nx: usize::MAX, ny: 2 }));
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub enum CsubError {
+   EmptyArray,
+   TooFewRows { array: &'static str, rows: usize, ny: usize },
+   RowTooShort { array: &'static str, row: usize, len: usize, nx: usize },
+   BufferTooSmall { array: &'static str, len: usize, needed: usize },
+   OutOfBounds { ix: usize, iy: usize, nx: usize, ny: usize },
+   TooManyElements { nx: usize, ny: usize },
+}
+
+impl fmt::Display for CsubError {
+   fn fmt (&self,f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         CsubError::EmptyArray =>
+            write!(f,"array dimensions are zero, nothing to do"),
+         CsubError::TooFewRows { array,rows,ny } =>
+            write!(f,"{} array has {} rows, but {} are needed",array,rows,ny),
+         CsubError::RowTooShort { array,row,len,nx } =>
+            write!(f,"row {} of {} array has {} elements, but {} are needed",
+                                                             row,array,len,nx),
+         CsubError::BufferTooSmall { array,len,needed } =>
+            write!(f,"{} array has {} elements, but {} are needed",
+                                                             array,len,needed),
+         CsubError::OutOfBounds { ix,iy,nx,ny } =>
+            write!(f,"element ({},{}) is outside the {} by {} array",ix,iy,nx,ny),
+         CsubError::TooManyElements { nx,ny } =>
+            write!(f,"{} rows of {} elements is more than can be indexed",ny,nx),
+      }
+   }
+}
+
+impl Error for CsubError {}
+
+//  ----------------------------------------------------------------------------
+//
+//                      C h e c k  D i m e n s i o n s
+//
+//   Checks that a 2D array held as a vector of vectors has at least ny rows,
+//   each of at least nx elements, returning an error if it doesn't. The
+//   array name is used in the error.
+
+pub fn check_dimensions (array: &[Vec<f32>],name: &'static str,nx: usize,
+                                            ny: usize) -> Result<(),CsubError> {
+   if array.len() < ny {
+      return Err(CsubError::TooFewRows { array: name, rows: array.len(), ny });
+   }
+   for (row,values) in array.iter().take(ny).enumerate() {
+      if values.len() < nx {
+         return Err(CsubError::RowTooShort { array: name,row,len: values.len(),nx });
+      }
+   }
+   Ok(())
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o CsubError implements std::error::Error, so it can be returned from a
+     function that returns Result<_,Box<dyn Error>> using the ? operator,
+     and works with the various error handling crates that build on the
+     standard trait. The Display implementation gives a message suitable
+     for printing directly, which is all crsmain1d.rs does with it. The
+     doctests on CsubError, run through crslib.rs with rustdoc --test, check
+     the exact message for each variant and that an error passed on by ?
+     into a Box<dyn Error> can be got back with downcast_ref().
+
+   o Zero dimensions are treated as an error, since although there's
+     nothing wrong with doing nothing, a caller passing a zero dimension
+     has almost certainly made a mistake.
+
+*/
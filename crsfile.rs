@@ -0,0 +1,264 @@
+//
+//                           c r s f i l e . r s
+//
+// Summary:
+//    Binary file input and output for the 2D array access tests in Rust.
+//
+// Introduction:
+//    The test programs in this study normally generate their own input data
+//    and check their own results. Sometimes, though, it is useful to be able to
+//    save the results of a test to a file, so that they can be compared with
+//    the results from a different version of the code, or from a different
+//    compiler. This module provides the routines used to read and write such
+//    files.
+//
+// This version:
+//    The files are simple raw binary files, containing nothing but the array
+//    values as 32-bit floating point numbers, one row after another, with no
+//    header. By default the values are written in little-endian byte order,
+//    which is the native order for most machines in use today, but each
+//    routine can also be asked to use big-endian order, which is the order
+//    used by FITS files, so the raw image data from a FITS file can be read
+//    directly.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added the choice of byte order.
+//    14th Oct 2026. Added read_f32_file_start().
+//    14th Oct 2026. Added write_pgm_file().
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This module is shared by a number of programs, not all of which use
+//  every routine in it.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::io;
+use std::io::{Read,Write};
+
+//  The byte order used for the f32 values in a file.
+
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Endian {
+   Little,
+   Big,
+}
+
+impl Endian {
+
+   //  Returns the byte order named by a command line value, "little" or
+   //  "big", or None if it is neither.
+
+   pub fn from_name (name: &str) -> Option<Endian> {
+      match name {
+         "little" => Some(Endian::Little),
+         "big" => Some(Endian::Big),
+         _ => None,
+      }
+   }
+
+   fn decode (self,chunk: &[u8]) -> f32 {
+      let bytes = [chunk[0],chunk[1],chunk[2],chunk[3]];
+      match self {
+         Endian::Little => f32::from_le_bytes(bytes),
+         Endian::Big => f32::from_be_bytes(bytes),
+      }
+   }
+
+   fn encode (self,value: f32) -> [u8; 4] {
+      match self {
+         Endian::Little => value.to_le_bytes(),
+         Endian::Big => value.to_be_bytes(),
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        R e a d  F 3 2  F i l e
+//
+//   Reads the whole of the named file and returns its contents as a vector
+//   of f32 values, in the byte order specified. It is an error if the file
+//   size is not a whole number of f32 values.
+
+pub fn read_f32_file (path: &str,endian: Endian) -> io::Result<Vec<f32>> {
+   let bytes = fs::read(path)?;
+   if bytes.len() % 4 != 0 {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+         format!("{} has {} bytes, not a whole number of f32 values",
+                                                           path,bytes.len())));
+   }
+   let mut values = Vec::with_capacity(bytes.len() / 4);
+   for chunk in bytes.chunks_exact(4) {
+      values.push(endian.decode(chunk));
+   }
+   Ok(values)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                    R e a d  F 3 2  F i l e  S t a r t
+//
+//   Reads up to count values from the start of the named file, in the byte
+//   order specified, without reading any more of the file than that. Returns
+//   the values read - fewer than count if the file is too small - and the
+//   number of values the whole file holds. As for read_f32_file(), it is an
+//   error if the file size is not a whole number of f32 values.
+
+pub fn read_f32_file_start (path: &str,count: usize,endian: Endian)
+                                             -> io::Result<(Vec<f32>,usize)> {
+   let file = fs::File::open(path)?;
+   let len = file.metadata()?.len() as usize;
+   if !len.is_multiple_of(4) {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+         format!("{} has {} bytes, not a whole number of f32 values",path,len)));
+   }
+   let mut values = vec![0.0f32; count.min(len / 4)];
+   read_f32_values(&mut io::BufReader::new(file),&mut values,endian)?;
+   Ok((values,len / 4))
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       W r i t e  F 3 2  F i l e
+//
+//   Writes a 2D array, passed as a vector of rows, to the named file, one
+//   row after another, in the byte order specified.
+
+pub fn write_f32_file (path: &str,array: &[Vec<f32>],endian: Endian) -> io::Result<()> {
+   let mut writer = io::BufWriter::new(fs::File::create(path)?);
+   for row in array {
+      for value in row {
+         writer.write_all(&endian.encode(*value))?;
+      }
+   }
+   writer.flush()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        W r i t e  P G M  F i l e
+//
+//   Writes a 2D array, passed as a vector of rows, to the named file as a
+//   binary (P5) PGM greyscale image, with one byte per pixel. The values are
+//   scaled so the smallest in the array is black and the largest is white.
+//   If they are all the same, the image is mid-grey, and any values that
+//   aren't finite are written as black. The first row is the top of the
+//   image, as for any other PGM file. It is an error if the rows are not
+//   all the same length.
+
+pub fn write_pgm_file (path: &str,array: &[Vec<f32>]) -> io::Result<()> {
+   let width = array.first().map_or(0,|row| row.len());
+   if array.iter().any(|row| row.len() != width) {
+      return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                "rows of different lengths can't be an image"));
+   }
+   let finite = array.iter().flatten().filter(|value| value.is_finite());
+   let (min,max) = finite.fold((f32::INFINITY,f32::NEG_INFINITY),
+                           |(min,max),&value| (min.min(value),max.max(value)));
+   let range = max as f64 - min as f64;
+   let mut writer = io::BufWriter::new(fs::File::create(path)?);
+   write!(writer,"P5\n{} {}\n255\n",width,array.len())?;
+   for row in array {
+      let pixels: Vec<u8> = row.iter().map(|&value| {
+         if !value.is_finite() {
+            0
+         } else if range > 0.0 {
+            (255.0 * (value as f64 - min as f64) / range).round() as u8
+         } else {
+            128
+         }
+      }).collect();
+      writer.write_all(&pixels)?;
+   }
+   writer.flush()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       R e a d  F 3 2  V a l u e s
+//
+//   Reads exactly enough values from a reader to fill the slice passed. This
+//   is used to read a file a block at a time, rather than all at once.
+
+pub fn read_f32_values<R: Read> (reader: &mut R,values: &mut [f32],
+                                                 endian: Endian) -> io::Result<()> {
+   let mut bytes = vec![0u8; values.len() * 4];
+   reader.read_exact(&mut bytes)?;
+   for (value,chunk) in values.iter_mut().zip(bytes.chunks_exact(4)) {
+      *value = endian.decode(chunk);
+   }
+   Ok(())
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      W r i t e  F 3 2  V a l u e s
+//
+//   Writes the values in a slice to a writer, the counterpart of
+//   read_f32_values().
+
+pub fn write_f32_values<W: Write> (writer: &mut W,values: &[f32],
+                                                    endian: Endian) -> io::Result<()> {
+   let mut bytes = Vec::with_capacity(values.len() * 4);
+   for value in values {
+      bytes.extend_from_slice(&endian.encode(*value));
+   }
+   writer.write_all(&bytes)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        W r i t e  T i m i n g s
+//
+//   Writes a set of timings to the named file as text, one per line. The
+//   timings are in nanoseconds, and are written as integers.
+
+pub fn write_timings (path: &str,times: &[f64]) -> io::Result<()> {
+   let mut writer = io::BufWriter::new(fs::File::create(path)?);
+   for time in times {
+      writeln!(writer,"{}",*time as u64)?;
+   }
+   writer.flush()
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The values are converted one at a time using from_le_bytes() and
+     to_le_bytes(), or their big-endian equivalents. It would be quicker to reinterpret the whole buffer in
+     one go, but that needs unsafe code and only works on little-endian
+     machines, and the speed of the file I/O isn't what's being tested here.
+
+   o The scaling for a PGM image is worked out in f64, since the difference
+     between the largest and smallest f32 values can overflow an f32. With
+     the usual input values, every output element is nx + ny, so the image
+     is a plain mid-grey. With an image written by --generate and read
+     back with --input, the values increase steadily along each row and
+     from one row to the next, and the output shows that as a gradient from
+     black at the top of the image to white at the bottom.
+
+*/
@@ -0,0 +1,213 @@
+//
+//                            c r s l i b . r s
+//
+// Summary:
+//    Library root for the Rust 2D array access routines.
+//
+// Introduction:
+//    The Rust test routines in this study are normally built straight into the
+//    test programs, each of which includes the routines it needs using mod
+//    declarations. This file lets the routines be built as a library instead,
+//    so they can be used by other code, and so the examples in their
+//    documentation can be run as tests. Each version of the routine is in
+//    its own module, as it is for the test programs, but since most of them
+//    are called csub() they are also re-exported here under distinct names,
+//    so code using the library can simply call crslib::csub_iter(), say,
+//    and try different versions just by changing the name.
+//
+// Building:
+//    rustc -O --crate-type=rlib crslib.rs
+//
+//    builds libcrslib.rlib, which another program can use through
+//    'extern crate crslib;' by building it with
+//
+//    rustc -O --extern crslib=libcrslib.rlib program.rs
+//
+//    The examples in the documentation are run as tests, once the library
+//    has been built, by
+//
+//    rustdoc --test -L . crslib.rs
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Now includes all the versions of csub(), re-exported
+//                   under distinct names.
+//    14th Oct 2026. crsmain1d.rs now uses the library.
+//    14th Oct 2026. csub1d_checked() now comes from crssub1d_checked.rs.
+//                   Notes on building crssub1d.rs by itself corrected.
+//    14th Oct 2026. crssub_prefetch is only included in x86_64 builds with
+//                   the 'prefetch' feature.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! The versions of csub() re-exported here can be swapped for one another
+//! freely, since they all produce the same results.
+//!
+//! ```
+//! let input_array = vec![vec![1.0f32; 100]; 20];
+//! let mut naive_output = vec![vec![0.0f32; 100]; 20];
+//! let mut iter_output = vec![vec![0.0f32; 100]; 20];
+//! crslib::csub_naive(&input_array,100,20,&mut naive_output);
+//! crslib::csub_iter(&input_array,100,20,&mut iter_output);
+//! assert_eq!(naive_output,iter_output);
+//! ```
+
+pub mod crserror;
+pub mod crsrandom;
+pub mod crssub;
+pub mod crssub1d;
+pub mod crssub1d_checked;
+pub mod crssub1d_chunks;
+pub mod crssub1d_convert;
+pub mod crssub1d_enumerate;
+pub mod crssub1d_lazy;
+pub mod crssub1d_unsafe;
+pub mod crssub1d_view;
+pub mod crssub_auto;
+pub mod crssub_boxed;
+pub mod crssub_branchy;
+pub mod crssub_checksum;
+pub mod crssub_cow;
+pub mod crssub_diagonal;
+pub mod crssub_div;
+pub mod crssub_dual;
+pub mod crssub_falseshare;
+pub mod crssub_fma;
+pub mod crssub_fold;
+pub mod crssub_generic;
+pub mod crssub_hint;
+pub mod crssub_iter;
+pub mod crssub_itercount;
+pub mod crssub_lenbound;
+pub mod crssub_memcpy;
+pub mod crssub_morton;
+pub mod crssub_prefix;
+pub mod crssub_recursive;
+pub mod crssub_refcell;
+pub mod crssub_rowloop;
+pub mod crssub_rowptr;
+#[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+pub mod crssub_prefetch;
+pub mod crssub_saturating;
+pub mod crssub_simd;
+pub mod crssub_simd_vertical;
+pub mod crssub_sparse;
+pub mod crssub_strided;
+pub mod crssub_threads;
+pub mod crssub_twopass;
+pub mod crssub_unsafe;
+pub mod crssub_vecarray;
+pub mod crssub_vector;
+
+pub use crserror::CsubError;
+
+//  The versions for a Vec<Vec<f32>> array, all called in the same way (apart
+//  from any extra arguments noted), with the names used for them by the
+//  --kernel option of crsmain.rs where there is one.
+
+pub use crssub::csub as csub_naive;
+pub use crssub::csub_checked as csub_naive_checked;
+pub use crssub::csub_nz as csub_naive_nz;           // NonZeroUsize dimensions.
+pub use crssub_iter::csub as csub_iter;
+pub use crssub_itercount::csub as csub_itercount;
+pub use crssub_unsafe::csub as csub_unsafe;
+pub use crssub_rowptr::csub as csub_rowptr;
+pub use crssub_recursive::{csub as csub_recursive,csub_tiled};
+pub use crssub_hint::csub as csub_hint;
+#[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+pub use crssub_prefetch::csub as csub_prefetch;
+pub use crssub_checksum::csub as csub_checksum;     // Returns the sum.
+pub use crssub_lenbound::csub as csub_lenbound;
+pub use crssub_threads::csub as csub_threads;       // Number of threads.
+pub use crssub_dual::csub_dual;                     // Two output arrays.
+pub use crssub_twopass::csub as csub_twopass;
+pub use crssub_memcpy::csub as csub_memcpy;
+pub use crssub_branchy::csub as csub_branchy;       // Threshold.
+pub use crssub_div::csub as csub_div;
+pub use crssub_diagonal::csub as csub_diagonal;
+pub use crssub_morton::{csub as csub_morton,csub_columns};
+pub use crssub_vector::csub as csub_vector;
+pub use crssub_fma::csub as csub_fma;
+pub use crssub_auto::csub as csub_auto;             // Picks one of the others.
+pub use crssub_sparse::csub as csub_sparse;         // List of coordinates.
+pub use crssub_sparse::csub_checked as csub_sparse_checked;
+pub use crssub_strided::csub as csub_strided;       // Stride.
+pub use crssub_simd_vertical::csub as csub_vertical;
+pub use crssub_simd::{csub as csub_simd,Tail};      // Tail handling.
+pub use crssub_prefix::csub as csub_prefix;
+pub use crssub_fold::csub as csub_fold;         // Returns the sum.
+pub use crssub_falseshare::csub as csub_falseshare; // Number of threads.
+pub use crssub_rowloop::{csub_iter as csub_row_iter,csub_index as csub_row_index};
+
+//  The versions for a flat f32 array of ny rows of nx elements.
+
+pub use crssub1d::csub1d as csub_flat;
+pub use crssub1d_checked::csub1d_checked as csub_flat_checked;
+pub use crssub1d::csub1d_block as csub_flat_block;
+pub use crssub1d_unsafe::csub1d as csub_flat_unsafe;
+pub use crssub1d_chunks::csub1d as csub_flat_chunks;
+pub use crssub1d_convert::{csub_convert,csub_narrow,csub_i16};  // To f64, to f32, i16 in.
+pub use crssub1d_view::csub1d_view as csub_flat_view;  // Row strides.
+pub use crssub1d_enumerate::csub1d as csub_flat_enumerate;
+pub use crssub1d_lazy::csub_iter as csub_flat_lazy;
+pub use crssub_cow::csub_cow;
+
+//  The versions for other element types and array representations.
+
+pub use crssub_generic::{csub as csub_generic,Element};
+pub use crssub_saturating::{csub_saturating,csub_wrapping};
+pub use crssub_vecarray::csub as csub_vecarray;
+pub use crssub_boxed::csub as csub_boxed;
+pub use crssub_refcell::{csub as csub_refcell,csub_row as csub_refcell_row};
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The routines themselves are left in the separate files used by the test
+     programs, so the programs and the library are built from exactly the
+     same code. crsmain1d.rs uses the library, through 'extern crate
+     crslib', as a program using it as a dependency would, and is built
+     with -L pointing at libcrslib.rlib. The other test programs still
+     include the routines directly rather than using the library. That
+     isn't just to keep them buildable with a single rustc command: a
+     function that isn't generic and isn't marked #[inline] can't normally
+     be inlined into code in another crate, so calling csub() from the
+     library would add a function call the timings have always been made
+     without, and could stop the optimiser taking advantage of what it
+     knows about the calling loop. crsmain1d.rs only makes a few calls
+     and checks the results, so for it that doesn't matter. The 'crate::'
+     paths used by the routines to refer to crserror and to each other work
+     for the test programs and for this library, since each declares those
+     modules at the top level. They wouldn't work for a file built as a
+     program by itself, so the one file that is, crssub1d.rs, doesn't use
+     any.
+
+   o crssub1d.rs has a main() of its own, so that it can be built as a
+     program by itself with just 'rustc crssub1d.rs'. For a while it also
+     held csub1d_checked(), which needs crserror, and that stopped it
+     building alone, so csub1d_checked() is now in crssub1d_checked.rs. In
+     the library the main() is just an unused private function, so rustc
+     gives a warning about it that can be ignored.
+
+*/
@@ -45,13 +45,491 @@
 //    rustc crsmain.rs         or, for optimised code:
 //    rustc -O -C target-cpu=native -C opt-level=3 crsmain.rs
 //
+//    The default version of csub() in crssub.rs can be built to use unchecked
+//    array accesses, by enabling the 'unchecked' feature, eg:
+//
+//    rustc -O --cfg 'feature="unchecked"' crsmain.rs
+//
+//    To make the loop in csub() easy to find in the generated assembler, it
+//    can be bracketed by comment markers using the 'markers' feature. This
+//    is for analysis only, since it changes the generated code, eg:
+//
+//    rustc -O --cfg 'feature="markers"' --emit asm crsmain.rs
+//
+//    The --async option, which times calls handed to another thread and
+//    waited for, as async code makes them, is only available with the
+//    'async' feature, eg:
+//
+//    rustc -O --cfg 'feature="async"' crsmain.rs
+//
+//    The prefetch kernel uses an x86_64 intrinsic, so it is only built for
+//    x86_64, and only with the 'prefetch' feature, eg:
+//
+//    rustc -O --cfg 'feature="prefetch"' crsmain.rs
+//
+//    If the --auto-name option is to be used, the compiler version and the
+//    optimisation level should be passed in environment variables, so they
+//    are built into the program, eg:
+//
+//    CRS_RUSTC_VERSION="$(rustc --version)" CRS_OPT_LEVEL=3 \
+//                                         rustc -C opt-level=3 crsmain.rs
+//
 // Invocation:
-//    ./crsmain irpt nx ny
+//    ./crsmain [options] irpt ny nx
 //
 //    where:
 //      irpt  is the number of times the subroutine is called - default 100000.
-//      nx    is the number of columns in the array tested - default 2000.
+//            This can include underscores, as in 1_000_000, or be given in
+//            scientific notation, as in 1e6, so long as it is a whole number.
 //      ny    is the number of rows in the array tested - default 10.
+//      nx    is the number of columns in the array tested - default 2000.
+//
+//    Note that the number of rows comes first, as for crsmain1d. It is easy
+//    to get these the wrong way round, and --size (see below) can be used
+//    instead, giving the width first in the usual way.
+//
+//    Either nx or ny can be zero, for example to time just the overhead of
+//    the calls, but then there is nothing to check the output against, so
+//    the program ends with "Empty array, nothing to verify" and a status
+//    of 2, rather than appearing to have succeeded.
+//
+//    If the test takes less than MIN_NS_PER_ELEMENT nanoseconds for each
+//    element processed, which no machine can manage, a warning is printed
+//    that the compiler has probably removed the calls, or most of them,
+//    since their results are never used. The result is still recorded.
+//
+//    and the options, which begin with '--' and can appear anywhere on the
+//    command line, are:
+//      --size=WxH  sets the array size as W columns by H rows, as in
+//                  --size=2000x10, in place of the ny and nx arguments,
+//                  which are then ignored if given. Both must be whole
+//                  numbers greater than zero, and anything else, such as
+//                  2000x or x10, is an error.
+//      --arrays=K  allocates K independent pairs of input and output arrays
+//                  and cycles through them on successive calls, so the total
+//                  working set is K times the size of a single pair. This
+//                  can be used to push the working set out of the cache
+//                  without changing the size of the individual arrays.
+//                  Default 1.
+//      --kernel=NAME  selects the version of csub() to be called, one of:
+//                     csub       - the default, in crssub.rs
+//                     iter       - crssub_iter.rs
+//                     unsafe     - crssub_unsafe.rs
+//                     prefetch   - crssub_prefetch.rs, x86_64 with the
+//                                  'prefetch' feature only (see Building)
+//                     checksum   - crssub_checksum.rs
+//                     lenbound   - crssub_lenbound.rs
+//                     threads    - crssub_threads.rs
+//                     twopass    - crssub_twopass.rs
+//                     memcpy     - crssub_memcpy.rs, a plain copy with no
+//                                  index sum, as a baseline
+//                     dual       - crssub_dual.rs, writing two output arrays
+//                     branchy    - crssub_branchy.rs, only adds the index sum
+//                                  to input values above their median
+//                     div        - crssub_div.rs, divides by the index sum
+//                                  plus one, to load the divider, not memory
+//                     diagonal   - crssub_diagonal.rs, works along the
+//                                  anti-diagonals rather than the rows
+//                     itercount  - crssub_itercount.rs, iter with counters in
+//                                  place of enumerate(), compared with iter
+//                     hint       - crssub_hint.rs, checks the sizes once then
+//                                  uses std::hint::assert_unchecked() to let
+//                                  safe indexing go unchecked
+//                     op         - crssub_op.rs, the operation set by --op
+//                     vector     - crssub_vector.rs, single loops for one row
+//                                  or one column arrays, also timed for those
+//                     fma        - crssub_fma.rs, using a fused multiply-add
+//                     auto       - crssub_auto.rs, times csub, iter, unsafe
+//                                  and hint on its first call then uses the
+//                                  fastest, reporting the choice
+//                     sparse     - crssub_sparse.rs, only updates a list of
+//                                  random coordinates (see --updates), times
+//                                  given per element written
+//                     vertical   - crssub_simd_vertical.rs, eight rows at
+//                                  once, one row in each SIMD lane
+//                     prefix     - crssub_prefix.rs, also adds the previous
+//                                  output element, a running sum along each
+//                                  row that can't be vectorised
+//                     fold       - crssub_fold.rs, the checksum kernel's sum
+//                                  carried through the loops by fold()
+//                     falseshare - crssub_falseshare.rs, --threads threads
+//                                  taking interleaved columns, so they all
+//                                  write to the same cache lines
+//                     nz         - csub_nz() in crssub.rs, with NonZeroUsize
+//                                  dimensions (nx and ny must not be zero)
+//                     morton     - crssub_morton.rs, visits the elements in
+//                                  Morton, or Z-order
+//                     simd       - crssub_simd.rs, eight elements of a row at
+//                                  a time, the row ends handled as set by
+//                                  --tail
+//                     rowptr     - crssub_rowptr.rs, unsafe with a pointer
+//                                  to each row worked out once per row
+//                     recursive  - crssub_recursive.rs, splits the rows in
+//                                  half down to a block of a few rows
+//                  Most of the others are also timed against csub, or the
+//                  version they are closest to, after checking their output.
+//                  --kernel-list lists them all. The dual kernel only
+//                  supports the normal test, ie not --latency, --raw-timings
+//                  or --cycles.
+//      --auto-kernel=NAME  with --kernel=auto, makes it use the named version
+//                  (csub, iter, unsafe or hint) without any calibration.
+//      --updates=N  with --kernel=sparse, the number of coordinates in the
+//                  list of elements to update. These are chosen at random,
+//                  using the --seed value if given, and can repeat. The
+//                  default is one element in a hundred.
+//      --op=OP     selects the operation used to combine each input value
+//                  with its index sum: add (the default, the normal test),
+//                  mul (multiplies by the index sum), div (divides by the
+//                  index sum plus one), copy (ignores the index sum), or
+//                  sqadd (adds the square of the index sum). This implies
+//                  --kernel=op.
+//      --tail=TAIL  selects how the simd kernel handles the elements left at
+//                  the end of each row when nx is not a multiple of eight:
+//                  scalar (the default, an ordinary loop) or masked (a
+//                  final SIMD operation, masked to the elements needed).
+//                  This implies --kernel=simd.
+//      --kernel-list  lists the versions of csub() that can be selected
+//                  using --kernel, with a one-line description of each, and
+//                  then exits.
+//      --threads=N  sets the number of threads used by the threads and
+//                  falseshare kernels. The default is the number of
+//                  processors available, or 1, with a warning, if this
+//                  can't be determined. The threads are the standard
+//                  library's scoped threads, started by std::thread::scope()
+//                  for each call, not a rayon thread pool, since these
+//                  programs are built with plain rustc and can't use
+//                  external crates such as rayon.
+//      --scaling   with the threads kernel, also times the kernel for 1, 2,
+//                  4... threads up to the number set by --threads, and
+//                  prints the time and speedup relative to one thread for
+//                  each, giving a strong scaling curve for the array size.
+//                  With one thread the kernel runs the serial loop on the
+//                  calling thread, so that row is the serial baseline.
+//      --numa-first-touch  has the input and output arrays allocated and
+//                  initialised by a set of threads that divide up the rows
+//                  the same way the threads kernel does, so that on a NUMA
+//                  machine each block of rows should end up in memory local
+//                  to the thread that later processes it. This is a best
+//                  efforts optimisation that relies on the Linux first-touch
+//                  page placement policy, and on the threads not moving
+//                  between processors.
+//      --output=FILE  writes the final output array to the named file as raw
+//                  f32 values, one row after another. Two such files can be
+//                  compared using the crsdiff program (if little-endian).
+//      --pgm=FILE  writes the final output array to the named file as a PGM
+//                  greyscale image, scaled so the smallest value is black
+//                  and the largest white, which almost any image viewer can
+//                  display. This is written once the test is over, so
+//                  doesn't affect the timings.
+//      --csv=FILE  appends the result of the test - the version of csub(),
+//                  the array dimensions, the number of calls and the time
+//                  taken - to the named file as a line of CSV, writing a
+//                  header line first if the file is new. Options such as
+//                  --precision or --all, which replace the normal test,
+//                  don't write a result.
+//      --json=FILE  does the same, but as a line holding a JSON object.
+//      --auto-name  names the --csv or --json file automatically, using the
+//                  rustc version, the optimisation level and the date and
+//                  time, so runs with different compilers don't write to
+//                  the same file. Use --csv or --json without a file name to
+//                  choose the format; the default is CSV. The rustc version
+//                  and optimisation level are only known for certain if
+//                  they are passed in when building, as described above.
+//      --machine-readable  with --csv or --json, adds every setting that
+//                  affects the test to each result - the element type, the
+//                  number of warmup calls, how the input was filled, the
+//                  random seed, the burn-in time, the number of threads, the
+//                  processors the program may run on, the byte order, the
+//                  operation, the simd tail handling, and the rustc version
+//                  and optimisation level - so each line says everything
+//                  about how it was produced.
+//                  In a CSV file these go in an extra 'settings' column, and
+//                  such a file can't be mixed with results written without
+//                  it.
+//      --input=FILE  reads the input array from the named file, in the same
+//                  raw format, instead of generating it. The file must hold
+//                  at least nx * ny values. If it holds more, only the first
+//                  nx * ny are read, and a warning gives the number left
+//                  over, so the test can be run on the start of a larger
+//                  data file. With --arrays, each of the input arrays gets a
+//                  copy of the same data.
+//      --generate=FILE  instead of running any test, writes a test image of
+//                  ny rows of nx columns to the named file, in the raw format
+//                  used by --input, and exits. Each value is the number of
+//                  the element, counting along the rows from zero, so the
+//                  image is a steady gradient and any value shows where it
+//                  belongs (exactly, for up to 2^24 elements). The file
+//                  can then be read back using --input, with the same
+//                  --endian. The files have no header, FITS or otherwise,
+//                  so the dimensions have to be given again to read it.
+//      --compare-csv=OLD,NEW  instead of running any test, reads two files
+//                  of results written by --csv and, for each version of
+//                  csub() and array size in either of them, prints the time
+//                  per element from each file and the change from OLD to
+//                  NEW as a percentage. Changes for the worse of more than
+//                  REGRESSION_THRESHOLD are marked as regressions (in red,
+//                  if the output is to a terminal). Where a file has more
+//                  than one result for the same version and size, the last
+//                  one is used. Results found in only one of the files are
+//                  listed as such. Exits with a status of 1 if either file
+//                  can't be read.
+//      --baseline=FILE  once the test has run, looks in FILE, written by
+//                  --csv, for the last result for the same version of csub()
+//                  and array size, and prints the time per element of this
+//                  test alongside the percentage change from that result,
+//                  or 'same as the baseline' if that rounds to 0.0%,
+//                  marking it as a regression as --compare-csv would. If
+//                  there is no such result, or FILE can't be read, just the
+//                  time for this test is printed.
+//      --endian=ORDER  sets the byte order used by --input, --output and
+//                  --generate, either 'little' (the default) or 'big'.
+//                  Big-endian is the order used for the image data in FITS
+//                  files.
+//      --latency   times each call individually, having flushed the arrays
+//                  from the cache before each call, and reports the
+//                  distribution of the times. This measures the cost of a
+//                  single cold call, rather than the throughput of many
+//                  calls one after the other. The times aren't kept, only
+//                  running statistics - the minimum, maximum, mean and
+//                  standard deviation - so any number of calls can be timed,
+//                  unless --percentiles or --raw-timings is also given.
+//      --percentiles  with --latency, keeps all the times, as --raw-timings
+//                  does, so the median and the 90th and 99th percentiles
+//                  can be reported as well.
+//      --raw-timings=FILE  times each call individually and writes the
+//                  times, in nanoseconds, to the named file, one per line in
+//                  the order the calls were made, for external analysis. The
+//                  distribution of the times is also printed. This can be
+//                  combined with --latency.
+//      --bench-json=FILE  times each call individually, as --raw-timings
+//                  does, and writes the mean, standard deviation, median,
+//                  minimum and maximum time for a call, in seconds, to the
+//                  named file in the JSON format written by hyperfine's
+//                  --export-json option, so the time taken inside the
+//                  program can be handled by the same tools as the times for
+//                  whole processes. The file holds
+//                     {"results": [{"command": C, "mean": T, "stddev": T,
+//                                   "median": T, "min": T, "max": T}]}
+//                  where C is the command line, less the --bench-json
+//                  argument, and each T a number of seconds. Any existing
+//                  file is replaced. This can be combined with --latency.
+//      --repeat-pattern  changes the input array slightly before each call,
+//                  adding the repeat number to its first element, so each
+//                  call does genuinely different work. Only affects the
+//                  normal test and --cycles.
+//      --zero-output  sets every element of the output array to zero before
+//                  each call, with the time this takes included in the time
+//                  measured. csub() writes every element anyway, so this is
+//                  the cost of clearing an array defensively when there's no
+//                  need. Affects the normal test, --cycles, --latency and
+//                  --raw-timings.
+//      --seed=N    fills the input arrays with pseudo-random values between
+//                  0 and 1, generated from the seed N, instead of the usual
+//                  values, unless --input is also given.
+//      --seed-per-repeat  refills one row of the input array with new random
+//                  values before each call, from a generator reseeded from
+//                  the seed and the repeat number, so the data is genuinely
+//                  different for every call. If --seed isn't given, the
+//                  arrays are filled using a seed of 1. For the normal test,
+//                  the time taken by the refills is measured separately and
+//                  subtracted. Only affects the normal test and --cycles,
+//                  and with --cycles the refills are included in the count.
+//      --affinity-sweep  before the normal test, runs the test pinned to
+//                  each processor the program is allowed to use in turn,
+//                  timing each call individually, and lists the median time
+//                  per call for each processor, marking the fastest and the
+//                  slowest. This shows up differences between the processors
+//                  of a hybrid (performance and efficiency core) machine, or
+//                  ones due to the cache layout. Only available on Linux.
+//      --profile   samples the program's instruction pointer every
+//                  PROFILE_INTERVAL_MICROS of processor time while the test
+//                  is run, and then prints the functions the samples fell in,
+//                  with the percentage of the samples in each, as a check
+//                  that the time is being spent where it should be. The
+//                  samples cover everything done for the selected kernel,
+//                  including warmups, checks and any comparisons made with
+//                  other versions, but not the setting up of the arrays.
+//                  Only available on Linux on x86_64.
+//      --sibling-load  before the normal test, pins the program to the first
+//                  processor it is allowed to use and times nrpt calls,
+//                  then starts a thread pinned to that processor's SMT
+//                  (hyperthreading) sibling that repeatedly updates a
+//                  SIBLING_LOAD_BYTES buffer, one cache line at a time, and
+//                  times nrpt calls again. Both times are printed, with their
+//                  ratio, showing how much the test suffers when the other
+//                  half of its core is busy, as it may well be on a shared
+//                  machine. The load competes for the core's execution
+//                  units and for its level 1 and 2 caches. Only available
+//                  on Linux, and skipped if the processor has no sibling.
+//      --runs=R    before the normal test, makes R complete measurements,
+//                  each with its own warmup followed by nrpt individually
+//                  timed calls, pausing RUN_SPACING_MILLIS between them, and
+//                  prints the median time per call for each, together with
+//                  the drift - the difference between the largest and the
+//                  smallest median, as a percentage of the smallest. This
+//                  is compared with the typical variation within a run, to
+//                  tell longer term changes in the machine's speed, such as
+//                  thermal throttling or other programs starting up, from
+//                  the jitter seen in any one run. Each run's warmup is the
+//                  same as for --warmup if that is given, or RUN_WARMUPS
+//                  calls if not.
+//      --check-reference-sum  after the test, also adds up all the elements
+//                  of each output array used and compares the total with the
+//                  value expected, nx * ny * (nx + ny), worked out without
+//                  looking at the arrays. With the usual input values, every
+//                  output element is nx + ny. Prints both totals, and exits
+//                  with a status of 1 if they differ. Only used with the usual
+//                  input values and a version of csub() that adds the index
+//                  sum, so not with --input, --seed, --seed-per-repeat,
+//                  --repeat-pattern, or these kernels:
+//                     memcpy    - copies the input with no index sum
+//                     div       - divides by the index sum plus one
+//                     branchy   - adds the index sum to some elements only
+//                     sparse    - only updates the --updates elements
+//                     prefix    - output is a running sum along each row
+//                     op        - unless --op=add is given
+//                  The check is skipped, with a message, for these.
+//      --exclusive[=wait]  takes a lock on the file crs_bench.lock in the
+//                  system's temporary directory before doing anything else,
+//                  and holds it until the program ends, so two programs run
+//                  with --exclusive can't run their tests at the same time
+//                  and spoil each other's timings. If another program already
+//                  has the lock, this one gives up with a message, or with
+//                  --exclusive=wait, waits until it can have the lock.
+//      --caches    lists the sizes of the data caches, as reported by Linux,
+//                  and says whether the working set for the test - all the
+//                  input and output arrays - fits in each of them. This helps
+//                  explain where the jumps in the time per element come from
+//                  as the arrays get larger. Does nothing if the cache sizes
+//                  can't be found.
+//      --page-faults  before the test proper, makes two extra calls, timing
+//                  each and counting the minor and major page faults that
+//                  happen during it, and reports the counts for both. The
+//                  first call to touch a newly allocated page of memory takes
+//                  a page fault for it, which is the first-touch cost that
+//                  warming up is meant to get out of the way, and this shows
+//                  how much of it is left by the time csub() is first called.
+//                  Only available on Linux.
+//      --cycles    uses the x86_64 time stamp counter to count the cycles
+//                  taken by all the calls, and reports the number of cycles
+//                  per element. Note that on modern processors this counts
+//                  reference cycles at a constant rate, not the actual core
+//                  clock cycles, which vary with the clock frequency. Only
+//                  available on x86_64.
+//      --roofline=GBPS  given the peak memory bandwidth of the machine in
+//                  GBytes/sec, as measured by something like the STREAM
+//                  benchmark, times the normal test and prints the time it
+//                  would take if limited only by memory bandwidth, together
+//                  with the measured time and the fraction of the peak
+//                  bandwidth achieved.
+//      --vs-memcpy  times the normal test, then times the same number of
+//                  calls to the memcpy version of csub(), which just copies
+//                  the arrays, and reports the time as a multiple of the
+//                  time for the copy.
+//      --warmup[=MAX]  before the test proper, makes warmup calls, timing
+//                  each one, until two successive calls take times within
+//                  10% of each other, or until MAX calls have been made
+//                  (default 100), and reports how many were needed. These
+//                  calls are not included in the timings, and are intended
+//                  to absorb start-up effects such as the page faults taken
+//                  the first time a newly allocated array is touched.
+//      --burn-in=MILLIS  before any warmup calls, calls csub() repeatedly for
+//                  the given number of milliseconds, to give the processor
+//                  time to reach the clock frequency it will run at under
+//                  sustained load, and reports the number of calls made and
+//                  the times for the first and last of them. Power saving
+//                  can leave the processor running slowly until it has been
+//                  busy for a while, which a few warmup calls may not be
+//                  enough to get past. None of these calls are timed as
+//                  part of the test.
+//      --row-headers  instead of calling csub(), times a loop that reads
+//                  just the first element of each row of the input array,
+//                  and then a loop that reads every element, and compares
+//                  the two. The first is dominated by the cost of following
+//                  the outer vector's pointers to each row, which is the
+//                  extra indirection a vector of vectors involves.
+//      --precision  compares the single and double precision versions of
+//                  the generic csub() in crssub_generic.rs, timing each and
+//                  printing the time per element and the memory bandwidth
+//                  (counting both the read and the write) side by side.
+//      --dtype=TYPE  instead of the normal test, times the generic csub() in
+//                  crssub_generic.rs for arrays of the given element type,
+//                  f32, f64, i32 or u8, and prints the time per element. Each
+//                  type has its own compiled copy of csub().
+//      --async     instead of the normal test, times nrpt calls to csub()
+//                  made directly, and then nrpt calls each handed to another
+//                  thread and waited for, the way async code would call it
+//                  using something like tokio's spawn_blocking(), and prints
+//                  both times and the extra time per call. This is not tokio:
+//                  programs built with plain rustc can't use it, so the calls
+//                  go through the single thread and block_on() in crsasync.rs,
+//                  and the extra time is that of the hand-off between the two
+//                  threads, not the overhead of any real async runtime, which
+//                  would add its own. This needs the 'async' feature, as
+//                  described above.
+//      --all       instead of the normal test, compares the nested layout
+//                  used by crssub.rs with the flat layout used by crssub1d.rs.
+//                  Both get the same warmup, and then the calls alternate,
+//                  one nested, one flat, each timed individually, so any
+//                  slow drift in the state of the machine affects both
+//                  equally. The distribution of the times is printed for
+//                  each, together with the ratio of the medians.
+//      --compare-layouts  instead of the normal test, sets up the same input
+//                  values in four different layouts - Vec<Vec<f32>>, used by
+//                  crssub.rs, a flat Vec<f32>, used by crssub1d.rs,
+//                  Vec<Box<[f32]>>, used by crssub_boxed.rs, and
+//                  Vec<[f32; N]>, used by crssub_vecarray.rs - and times
+//                  the version of csub() for each in the same way as --all,
+//                  with the same warmup and with the calls to the four
+//                  taking turns. It then prints a table of the layouts,
+//                  fastest first, with the median and minimum time per call,
+//                  the mean with its 95% confidence interval, the time per
+//                  element and the ratio to the fastest. N has
+//                  to be fixed when the program is compiled, and is set by
+//                  LAYOUT_COLUMNS to 2000, the default number of columns;
+//                  for any other number of columns, Vec<[f32; N]> is left
+//                  out, with a warning.
+//      --matrix    instead of the normal test, times each of the versions of
+//                  csub() listed in MATRIX_KERNELS for the same array size
+//                  (or, with --sweep, for each of a range of sizes) and
+//                  prints a table of the time per element, with a row for
+//                  each size and a column for each version, followed by a
+//                  table of the speedup of each version relative to csub.
+//                  For each size, the calls are made in MATRIX_ROUNDS rounds,
+//                  each round timing a share of the repeats for every version
+//                  in turn, and the median of the rounds is used, so all the
+//                  versions see the machine in much the same state. With
+//                  --csv or --json, a result is written for each entry in
+//                  the table.
+//      --gnuplot=PREFIX  with --matrix, also writes the table of times per
+//                  element to PREFIX.dat, with the working set size in bytes
+//                  for each row, and a gnuplot script to PREFIX.plt that
+//                  plots them on a log scale. 'gnuplot PREFIX.plt' then
+//                  writes the chart to PREFIX.png. With --sweep, this shows
+//                  where the times change as the arrays outgrow each cache.
+//      --saturate  with --dtype=i32, instead times the two integer versions of
+//                  csub() in crssub_saturating.rs, one using saturating and
+//                  one using wrapping addition, with input values close to
+//                  the largest i32 value, so about half the additions
+//                  overflow. Checks both results and prints both times.
+//      --elemsize  times the generic csub() in crssub_generic.rs for arrays
+//                  of u8, f32 and f64 elements with the same dimensions, and
+//                  prints the time per element and the memory bandwidth for
+//                  each. If the test is limited by memory bandwidth, the
+//                  bandwidths should be similar and the times per element
+//                  should go up with the element size.
+//      --timeout=SECONDS  sets a time limit for the test. If the calls to
+//                  csub() haven't finished in that time, a message is
+//                  printed and the program exits. With --precision,
+//                  --elemsize or --dtype, the limit applies to each timing
+//                  separately, and one that takes too long is reported as
+//                  timed out, and any remaining sizes in a sweep are still
+//                  tried. The default is no limit.
+//      --sweep     with --precision, --elemsize or --matrix, repeats the
+//                  comparison for a range of array widths, doubling from 16
+//                  up to nx, with the number of rows fixed. The number of
+//                  repeats is scaled so the same total number of elements is
+//                  processed for each width.
 //
 //    Note that Rust use row-major order; arrays are stored in memory so that
 //    the second index varies fastest. We want the array to be stored so that
@@ -59,11 +537,105 @@
 //    number (the X-value) as the second index when setting up the array.
 //
 // Author(s): Keith Shortridge, Keith@KnaveAndVarlet.com.au
+//            agent, agent@local (the changes dated 14th Oct 2026)
 //
 // History:
 //    13th Sep 2019. First properly commented version. KS.
 //    28th Oct 2019. Corrected code so defaults for irpt, nx and ny match those
 //                   in the description, ie 100000, 2000 and 10. KS.
+//    14th Oct 2026. Added the --arrays option, and the general handling of
+//                   options that begin with '--'.
+//    14th Oct 2026. Added the --kernel option, so the different versions of
+//                   csub() can be compared using the same main routine.
+//    14th Oct 2026. Added the --output option.
+//    14th Oct 2026. Added the --latency option. The options are now held in
+//                   an Options structure.
+//    14th Oct 2026. Added the checksum kernel.
+//    14th Oct 2026. Added the --precision and --sweep options.
+//    14th Oct 2026. Added the lenbound kernel.
+//    14th Oct 2026. Added the --raw-timings option.
+//    14th Oct 2026. Added the threads kernel, and the --threads and
+//                   --numa-first-touch options.
+//    14th Oct 2026. Added the --cycles option.
+//    14th Oct 2026. Added the dual kernel.
+//    14th Oct 2026. Added the --repeat-pattern option.
+//    14th Oct 2026. Added the twopass kernel.
+//    14th Oct 2026. Added the --roofline option.
+//    14th Oct 2026. Added the memcpy kernel and the --vs-memcpy option.
+//    14th Oct 2026. Repeat count can now include underscores or be given in
+//                   scientific notation.
+//    14th Oct 2026. Added the --warmup option.
+//    14th Oct 2026. Added the --kernel-list option.
+//    14th Oct 2026. Added the --row-headers option.
+//    14th Oct 2026. Added the --input and --endian options.
+//    14th Oct 2026. Added the branchy kernel.
+//    14th Oct 2026. Added the --dtype option.
+//    14th Oct 2026. Added the --all option.
+//    14th Oct 2026. Added the --scaling option. This uses scoped threads
+//                   from std::thread, not a rayon pool.
+//    14th Oct 2026. Results checks now in check_output(), also used by
+//                   --scaling.
+//    14th Oct 2026. Timing summaries now include the coefficient of variation,
+//                   and flag unstable measurements.
+//    14th Oct 2026. Added the --saturate option.
+//    14th Oct 2026. Added the --csv, --json and --auto-name options.
+//    14th Oct 2026. Added the div kernel.
+//    14th Oct 2026. Added the --elemsize option.
+//    14th Oct 2026. Added the --timeout option.
+//    14th Oct 2026. Added the diagonal kernel.
+//    14th Oct 2026. Added the itercount kernel.
+//    14th Oct 2026. Added the --seed and --seed-per-repeat options.
+//    14th Oct 2026. Added the --affinity-sweep option.
+//    14th Oct 2026. Added the hint kernel.
+//    14th Oct 2026. Added the --page-faults option.
+//    14th Oct 2026. Added the --matrix option.
+//    14th Oct 2026. Added the op kernel and the --op option.
+//    14th Oct 2026. Added the --caches option.
+//    14th Oct 2026. Added the --check-reference-sum option.
+//    14th Oct 2026. Added the vector kernel.
+//    14th Oct 2026. Added the --exclusive option.
+//    14th Oct 2026. Added the fma kernel.
+//    14th Oct 2026. Default thread count falls back to 1 with a warning if
+//                   the number of processors can't be determined.
+//    14th Oct 2026. Added the --machine-readable option.
+//    14th Oct 2026. Added the auto kernel and the --auto-kernel option.
+//    14th Oct 2026. An empty array is reported as having nothing to verify,
+//                   with an exit status of 2.
+//    14th Oct 2026. Added the --gnuplot option.
+//    14th Oct 2026. Added the sparse kernel and the --updates option.
+//    14th Oct 2026. Added the 'async' feature and the --async option.
+//    14th Oct 2026. Added the --burn-in option.
+//    14th Oct 2026. --input uses the start of a file bigger than needed.
+//    14th Oct 2026. Added the vertical kernel.
+//    14th Oct 2026. Added the --compare-layouts option.
+//    14th Oct 2026. Added the --sibling-load option.
+//    14th Oct 2026. Added the prefix kernel.
+//    14th Oct 2026. Added the --zero-output option.
+//    14th Oct 2026. Added the --generate option.
+//    14th Oct 2026. Added the fold kernel.
+//    14th Oct 2026. Timing summaries now include the standard error of the
+//                   mean and a 95% confidence interval.
+//    14th Oct 2026. Added the falseshare kernel.
+//    14th Oct 2026. Added the --size option, and corrected the order of the
+//                   ny and nx arguments given above.
+//    14th Oct 2026. Added the --profile option.
+//    14th Oct 2026. Added the --runs option.
+//    14th Oct 2026. Added the nz kernel.
+//    14th Oct 2026. --latency keeps running statistics rather than all the
+//                   times, and added the --percentiles option.
+//    14th Oct 2026. Added the morton kernel.
+//    14th Oct 2026. Added the --pgm option.
+//    14th Oct 2026. Added the --compare-csv option.
+//    14th Oct 2026. Warns if the time per element is too short to be real.
+//    14th Oct 2026. Added the simd kernel and the --tail option.
+//    14th Oct 2026. Added the --baseline option.
+//    14th Oct 2026. Added the rowptr kernel.
+//    14th Oct 2026. Added the --bench-json option.
+//    14th Oct 2026. Added the recursive kernel.
+//    14th Oct 2026. The kernels timed against other versions of csub() all
+//                   use compare_kernels(), which also checks their results.
+//    14th Oct 2026. The prefetch kernel is only built for x86_64, with the
+//                   'prefetch' feature.
 //
 // Copyright (c) 2019 Knave and Varlet
 //
@@ -85,9 +657,243 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::io;
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+use std::process;
+use crssub_generic::Element;
+use crsfile::Endian;
+use std::sync::atomic;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration,Instant};
 
 mod crssub;
+mod crssub_iter;
+mod crssub_unsafe;
+#[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+mod crssub_prefetch;
+mod crssub_checksum;
+mod crssub_generic;
+mod crssub_lenbound;
+mod crssub_threads;
+mod crssub_dual;
+mod crssub_twopass;
+mod crssub_memcpy;
+mod crssub_branchy;
+mod crssub1d;
+mod crssub_saturating;
+mod crssub_div;
+mod crssub_diagonal;
+mod crssub_morton;
+mod crssub_itercount;
+mod crssub_hint;
+mod crssub_op;
+mod crssub_vector;
+mod crssub_fma;
+mod crssub_auto;
+mod crssub_sparse;
+mod crssub_simd_vertical;
+mod crssub_simd;
+mod crssub_rowptr;
+mod crssub_recursive;
+mod crssub_boxed;
+mod crssub_vecarray;
+mod crssub_prefix;
+mod crssub_fold;
+mod crssub_falseshare;
+mod crserror;
+mod crsfile;
+mod crstime;
+mod crsresult;
+mod crsrandom;
+mod crsos;
+mod crsprofile;
+#[cfg(feature = "async")]
+mod crsasync;
+
+//  The maximum number of warmup calls made by --warmup if no number is
+//  given, and how close the times for successive warmup calls have to be,
+//  as a fraction of the earlier time, for them to count as stable.
+
+const DEFAULT_MAX_WARMUPS: usize = 100;
+const WARMUP_TOLERANCE: f64 = 0.1;
+
+//  The number of warmup calls made for each layout by --all.
+
+const LAYOUT_WARMUPS: usize = 10;
+
+//  The interval of processor time between the samples taken by --profile,
+//  and the number of functions listed in the profile.
+
+const PROFILE_INTERVAL_MICROS: u64 = 1000;
+const PROFILE_FUNCTIONS: usize = 10;
+
+//  The size of the buffer updated by the load thread for --sibling-load, and
+//  the step between the words it updates, which is one 64 byte cache line.
+
+const SIBLING_LOAD_BYTES: usize = 8 * 1024 * 1024;
+const SIBLING_LOAD_STRIDE: usize = 64 / std::mem::size_of::<u64>();
+
+//  The pause between the measurements made by --runs, and the number of
+//  warmup calls made before each one if --warmup isn't given.
+
+const RUN_SPACING_MILLIS: u64 = 500;
+const RUN_WARMUPS: usize = 10;
+
+//  The number of columns in the Vec<[f32; N]> layout used by --compare-layouts,
+//  which has to be known at compile time.
+
+const LAYOUT_COLUMNS: usize = 2000;
+
+//  The coefficient of variation of a set of timings above which they are
+//  flagged as unstable.
+
+const CV_THRESHOLD: f64 = 0.05;
+
+//  The shortest time per element, in nanoseconds, that a test can really take.
+//  Anything faster means the calls have been optimised away. Even reading and
+//  writing the L1 cache with 512-bit vectors, at most two loads and one
+//  store a cycle allow about 16 elements a cycle, or 0.0125 ns an element
+//  at 5 GHz.
+
+const MIN_NS_PER_ELEMENT: f64 = 0.01;
+
+//  The fractional increase in the time per element beyond which --compare-csv
+//  and --baseline mark a result as a regression.
+
+const REGRESSION_THRESHOLD: f64 = 0.05;
+
+//  The exit status used if either array dimension is zero, so there is
+//  nothing to verify. Failed checks use a status of 1.
+
+const EMPTY_ARRAY_STATUS: i32 = 2;
+
+//  Set by the watchdog started by --timeout if the time limit is reached for
+//  one of the timings that can be abandoned rather than ending the program.
+
+static TIMED_OUT: atomic::AtomicBool = atomic::AtomicBool::new(false);
+
+//  The versions of csub() that can be selected using --kernel, with a short
+//  description of each, as listed by --kernel-list. Any new version added to
+//  the match on the kernel name in the main routine should be added here too.
+
+const KERNELS: &[(&str,&str)] = &[
+   ("csub","array[iy][ix] indexing on Vec<Vec<f32>> (crssub.rs)"),
+   ("iter","iterators over the rows and elements (crssub_iter.rs)"),
+   ("unsafe","get_unchecked on Vec<Vec<f32>> (crssub_unsafe.rs)"),
+   #[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+   ("prefetch","prefetches the input ahead of each access (crssub_prefetch.rs)"),
+   ("checksum","also returns the sum of the outputs (crssub_checksum.rs)"),
+   ("lenbound","loops bounded by the array lengths, not nx, ny (crssub_lenbound.rs)"),
+   ("threads","rows split between --threads threads (crssub_threads.rs)"),
+   ("dual","writes two output arrays in one pass (crssub_dual.rs)"),
+   ("twopass","clears the output, then adds in a second pass (crssub_twopass.rs)"),
+   ("memcpy","plain copy with no index sum, as a baseline (crssub_memcpy.rs)"),
+   ("branchy","index sum only above a threshold, to test branch costs (crssub_branchy.rs)"),
+   ("div","divides by the index sum plus one, compute bound (crssub_div.rs)"),
+   ("diagonal","works along the anti-diagonals, not the rows (crssub_diagonal.rs)"),
+   ("itercount","iter, but with counters instead of enumerate() (crssub_itercount.rs)"),
+   ("hint","checks sizes once, then assert_unchecked() hints (crssub_hint.rs)"),
+   ("op","uses the operation selected by --op (crssub_op.rs)"),
+   ("vector","single loops for one row or one column arrays (crssub_vector.rs)"),
+   ("fma","the same result using a fused multiply-add (crssub_fma.rs)"),
+   ("auto","times csub, iter, unsafe and hint, then uses the fastest (crssub_auto.rs)"),
+   ("sparse","only updates the elements in a list of --updates coordinates (crssub_sparse.rs)"),
+   ("vertical","eight rows at once, one in each SIMD lane (crssub_simd_vertical.rs)"),
+   ("prefix","running sum along each row, a serial dependency (crssub_prefix.rs)"),
+   ("fold","checksum in the same pass, using iterators and fold() (crssub_fold.rs)"),
+   ("falseshare","threads writing interleaved columns, to show false sharing (crssub_falseshare.rs)"),
+   ("nz","csub with NonZeroUsize dimensions, so zero is an error (crssub.rs)"),
+   ("morton","visits the elements in Morton (Z) order, not by rows (crssub_morton.rs)"),
+   ("simd","SIMD along the rows, with the row ends as set by --tail (crssub_simd.rs)"),
+   ("rowptr","unsafe, with one pointer to the start of each row (crssub_rowptr.rs)"),
+   ("recursive","splits the rows in half recursively, cache-oblivious (crssub_recursive.rs)"),
+];
+
+//  The versions of csub() timed by --matrix. These are the ones that take
+//  the standard arguments and give the standard results, and that make
+//  sense to time on a single thread. The first is the one the speedups are
+//  relative to.
+
+const MATRIX_KERNELS: &[&str] = &["csub","iter","unsafe",
+   #[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+   "prefetch",
+   "lenbound","twopass","itercount","hint","diagonal","vertical","morton","rowptr",
+   "recursive"];
+
+//  The number of rounds the calls are split into for each size by --matrix.
+
+const MATRIX_ROUNDS: usize = 5;
+
+//  The settings that control a test, set from the command line.
+
+#[derive(Clone)]
+struct Options {
+   nrpt: usize,
+   nx: usize,
+   ny: usize,
+   narrays: usize,
+   kernel_name: String,
+   auto_kernel: String,
+   updates: usize,
+   op: crssub_op::Op,
+   tail: crssub_simd::Tail,
+   output_file: String,
+   pgm_file: String,
+   csv_file: String,
+   json_file: String,
+   auto_name: bool,
+   machine_readable: bool,
+   input_file: String,
+   generate_file: String,
+   compare_csv: String,
+   baseline_file: String,
+   endian: Endian,
+   raw_timings: String,
+   bench_json: String,
+   latency: bool,
+   percentiles: bool,
+   nthreads: usize,
+   scaling: bool,
+   numa_first_touch: bool,
+   cycles: bool,
+   affinity_sweep: bool,
+   sibling_load: bool,
+   runs: usize,
+   profile: bool,
+   page_faults: bool,
+   caches: bool,
+   reference_sum: bool,
+   exclusive: bool,
+   exclusive_wait: bool,
+   repeat_pattern: bool,
+   zero_output: bool,
+   seed: Option<u64>,
+   seed_per_repeat: bool,
+   roofline: f64,
+   vs_memcpy: bool,
+   max_warmups: usize,
+   burn_in: u64,
+   kernel_list: bool,
+   threshold: f32,
+   row_headers: bool,
+   timeout: f64,
+   precision: bool,
+   elemsize: bool,
+   dtype: String,
+   saturate: bool,
+   all: bool,
+   compare_layouts: bool,
+   async_calls: bool,
+   matrix: bool,
+   gnuplot: String,
+   sweep: bool,
+}
 
 //  ----------------------------------------------------------------------------
 //
@@ -99,83 +905,3439 @@ fn main() {
    //  or values supplied on the command line. Collect the command line
    //  arguments into a string vector, then parse them if present, checking
    //  the results of the parsing. If invalid numbers are supplied, use the
-   //  original default values.
+   //  original default values. Any arguments that start with '--' are
+   //  options, and these are handled first and are not included in the
+   //  vector of positional arguments.
 
-   let mut nrpt = 100000;
-   let mut ny = 10;
-   let mut nx = 2000;
-   let args: Vec<String> = env::args().collect();
+   let mut options = Options {
+      nrpt: 100000,
+      ny: 10,
+      nx: 2000,
+      narrays: 1,
+      kernel_name: String::from("csub"),
+      auto_kernel: String::new(),
+      updates: 0,
+      op: crssub_op::Op::Add,
+      tail: crssub_simd::Tail::Scalar,
+      output_file: String::new(),
+      pgm_file: String::new(),
+      csv_file: String::new(),
+      json_file: String::new(),
+      auto_name: false,
+      machine_readable: false,
+      input_file: String::new(),
+      generate_file: String::new(),
+      compare_csv: String::new(),
+      baseline_file: String::new(),
+      endian: Endian::Little,
+      raw_timings: String::new(),
+      bench_json: String::new(),
+      latency: false,
+      percentiles: false,
+      nthreads: 0,
+      scaling: false,
+      numa_first_touch: false,
+      cycles: false,
+      affinity_sweep: false,
+      sibling_load: false,
+      runs: 1,
+      profile: false,
+      page_faults: false,
+      caches: false,
+      reference_sum: false,
+      exclusive: false,
+      exclusive_wait: false,
+      repeat_pattern: false,
+      zero_output: false,
+      seed: None,
+      seed_per_repeat: false,
+      roofline: 0.0,
+      vs_memcpy: false,
+      max_warmups: 0,
+      burn_in: 0,
+      kernel_list: false,
+      threshold: 0.0,
+      row_headers: false,
+      timeout: 0.0,
+      precision: false,
+      elemsize: false,
+      dtype: String::new(),
+      saturate: false,
+      all: false,
+      compare_layouts: false,
+      async_calls: false,
+      matrix: false,
+      gnuplot: String::new(),
+      sweep: false,
+   };
+   let mut size: Option<(usize,usize)> = None;
+   let mut args: Vec<String> = Vec::new();
+   for arg in env::args() {
+      if arg.starts_with("--") {
+         let (name,value) = match arg.find('=') {
+            Some(index) => (&arg[..index],&arg[index + 1..]),
+            None => (&arg[..],""),
+         };
+         match name {
+            "--size" => match parse_size(value) {
+               Ok(dimensions) => size = Some(dimensions),
+               Err(message) => {
+                  println!("{}",message);
+                  process::exit(1);
+               }
+            },
+            "--arrays" => match value.parse::<usize>() {
+               Ok(number) if number > 0 => options.narrays = number,
+               _ => println!("Arrays invalid, using {}",options.narrays),
+            },
+            "--kernel" => options.kernel_name = value.to_string(),
+            "--kernel-list" => options.kernel_list = true,
+            "--auto-kernel" => options.auto_kernel = value.to_string(),
+            "--updates" => match value.parse::<usize>() {
+               Ok(number) => options.updates = number,
+               _ => println!("Updates invalid, using the default"),
+            },
+            "--op" => match crssub_op::Op::from_name(value) {
+               Some(op) => {
+                  options.op = op;
+                  options.kernel_name = "op".to_string();
+               }
+               None => println!("Unknown operation {}, ignored",value),
+            },
+            "--tail" => match crssub_simd::Tail::from_name(value) {
+               Some(tail) => {
+                  options.tail = tail;
+                  options.kernel_name = "simd".to_string();
+               }
+               None => println!("Unknown tail handling {}, ignored",value),
+            },
+            "--output" => options.output_file = value.to_string(),
+            "--pgm" => options.pgm_file = value.to_string(),
+            "--csv" => options.csv_file = value.to_string(),
+            "--json" => options.json_file = value.to_string(),
+            "--auto-name" => options.auto_name = true,
+            "--machine-readable" => options.machine_readable = true,
+            "--input" => options.input_file = value.to_string(),
+            "--generate" => options.generate_file = value.to_string(),
+            "--compare-csv" => options.compare_csv = value.to_string(),
+            "--baseline" => options.baseline_file = value.to_string(),
+            "--endian" => match Endian::from_name(value) {
+               Some(endian) => options.endian = endian,
+               None => println!("Byte order invalid, using little-endian"),
+            },
+            "--latency" => options.latency = true,
+            "--percentiles" => options.percentiles = true,
+            "--raw-timings" => options.raw_timings = value.to_string(),
+            "--bench-json" => options.bench_json = value.to_string(),
+            "--threads" => match value.parse::<usize>() {
+               Ok(number) if number > 0 => options.nthreads = number,
+               _ => println!("Threads invalid, using the default"),
+            },
+            "--scaling" => options.scaling = true,
+            "--numa-first-touch" => options.numa_first_touch = true,
+            "--cycles" => options.cycles = true,
+            "--affinity-sweep" => options.affinity_sweep = true,
+            "--sibling-load" => options.sibling_load = true,
+            "--runs" => match value.parse::<usize>() {
+               Ok(number) if number > 0 => options.runs = number,
+               _ => println!("Runs invalid, using {}",options.runs),
+            },
+            "--profile" => options.profile = true,
+            "--page-faults" => options.page_faults = true,
+            "--caches" => options.caches = true,
+            "--check-reference-sum" => options.reference_sum = true,
+            "--exclusive" => {
+               options.exclusive = true;
+               match value {
+                  "" => {}
+                  "wait" => options.exclusive_wait = true,
+                  _ => println!("--exclusive={} not understood, not waiting",value),
+               }
+            }
+            "--repeat-pattern" => options.repeat_pattern = true,
+            "--zero-output" => options.zero_output = true,
+            "--seed" => match value.parse::<u64>() {
+               Ok(number) => options.seed = Some(number),
+               _ => println!("Seed invalid, ignored"),
+            },
+            "--seed-per-repeat" => options.seed_per_repeat = true,
+            "--vs-memcpy" => options.vs_memcpy = true,
+            "--warmup" => {
+               options.max_warmups = DEFAULT_MAX_WARMUPS;
+               if !value.is_empty() {
+                  match value.parse::<usize>() {
+                     Ok(number) if number > 1 => options.max_warmups = number,
+                     _ => println!("Maximum warmups invalid, using {}",
+                                                          options.max_warmups),
+                  }
+               }
+            },
+            "--burn-in" => match value.parse::<u64>() {
+               Ok(number) => options.burn_in = number,
+               _ => println!("Burn-in time invalid, ignored"),
+            },
+            "--roofline" => match value.parse::<f64>() {
+               Ok(number) if number > 0.0 => options.roofline = number,
+               _ => println!("Roofline bandwidth invalid, ignored"),
+            },
+            "--row-headers" => options.row_headers = true,
+            "--timeout" => match value.parse::<f64>() {
+               Ok(number) if number > 0.0 => options.timeout = number,
+               _ => println!("Timeout invalid, ignored"),
+            },
+            "--precision" => options.precision = true,
+            "--elemsize" => options.elemsize = true,
+            "--dtype" => options.dtype = value.to_string(),
+            "--saturate" => options.saturate = true,
+            "--all" => options.all = true,
+            "--compare-layouts" => options.compare_layouts = true,
+            "--matrix" => options.matrix = true,
+            "--sweep" => options.sweep = true,
+            "--async" => options.async_calls = true,
+            "--gnuplot" => options.gnuplot = value.to_string(),
+            _ => println!("Unknown option {} ignored",arg),
+         }
+      } else {
+         args.push(arg);
+      }
+   }
    if args.len() > 1 {
-      match args[1].parse::<usize>() {
-         Ok(number) => nrpt = number,
-         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      match parse_count(&args[1]) {
+         Some(number) => options.nrpt = number,
+         None => println!("Repeats invalid, using {}",options.nrpt),
       };
       if args.len() > 2 {
          match args[2].parse::<usize>() {
-            Ok(number) => ny = number,
-            Err(_error) => println!("Rows invalid, using {}",ny),
+            Ok(number) => options.ny = number,
+            Err(_error) => println!("Rows invalid, using {}",options.ny),
          };
          if args.len() > 3 {
             match args[3].parse::<usize>() {
-               Ok(number) => nx = number,
-               Err(_error) => println!("Columns invalid, using {}",nx),
+               Ok(number) => options.nx = number,
+               Err(_error) => println!("Columns invalid, using {}",options.nx),
             };
          }
       }
    }
+   if let Some((nx,ny)) = size {
+      if args.len() > 2 {
+         println!("Using --size={}x{}, the rows and columns arguments are ignored",nx,ny);
+      }
+      options.nx = nx;
+      options.ny = ny;
+   }
+
+   //  The lock, if wanted, is held for as long as the file is open, which is
+   //  until the end of the main routine. If the program exits by any other
+   //  means, including a panic, the operating system releases the lock.
+
+   let _lock = if options.exclusive {
+      match take_lock(options.exclusive_wait) {
+         Ok(file) => Some(file),
+         Err(message) => {
+            println!("{}",message);
+            process::exit(1);
+         }
+      }
+   } else {
+      None
+   };
+   if options.nthreads == 0 {
+      let uses_threads = options.kernel_name == "threads" || options.kernel_name == "falseshare"
+                                   || options.scaling || options.numa_first_touch;
+      options.nthreads =
+         crssub_threads::default_threads(thread::available_parallelism(),uses_threads);
+   }
+   if options.kernel_list {
+      for (name,description) in KERNELS.iter() {
+         println! ("{:>10}: {}",name,description);
+      }
+      return;
+   }
+   if !options.generate_file.is_empty() {
+      generate_image(&options);
+      return;
+   }
+   if !options.compare_csv.is_empty() {
+      compare_csv_files(&options.compare_csv);
+      return;
+   }
+   let (nrpt,nx,ny,narrays) = (options.nrpt,options.nx,options.ny,options.narrays);
    println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+   if options.caches {
+      report_caches(&options);
+   }
+
+   //  The precision comparison, the timing for a specified element type
+   //  and the layout comparison set up their own arrays, so are handled
+   //  separately.
+
+   if options.precision {
+      compare_precisions(&options);
+      return;
+   }
+   if options.elemsize {
+      compare_element_sizes(&options);
+      return;
+   }
+   if options.saturate {
+      if options.dtype == "i32" {
+         compare_saturation(&options);
+         return;
+      }
+      println!("--saturate is only used with --dtype=i32, ignored");
+   }
+   if !options.dtype.is_empty() {
+      time_dtype(&options);
+      return;
+   }
+   if options.all {
+      compare_layouts(&options);
+      return;
+   }
+   if options.compare_layouts {
+      rank_layouts(&options);
+      return;
+   }
+   if options.matrix {
+      time_matrix(&options);
+      return;
+   }
+   if options.async_calls {
+      #[cfg(feature = "async")]
+      {
+         compare_async(&options);
+         return;
+      }
+      #[cfg(not(feature = "async"))]
+      println!("--async needs the program built with the 'async' feature, ignored");
+   }
+   if !options.gnuplot.is_empty() {
+      println!("--gnuplot is only used with --matrix, ignored");
+   }
 
    //  Set up the input and output arrays, using single precision floating
-   //  point values.
+   //  point values. Normally there is just one of each, but if --arrays was
+   //  specified there are narrays independent pairs, each separately
+   //  allocated. (vec! clones its argument for each element, so each of the
+   //  rows of each array is a separate allocation.)
 
-   let mut in_array = vec![vec![0.0f32; nx]; ny];
-   let mut out_array = vec![vec![0.0f32; nx]; ny];
+   let mut in_arrays: Vec<Vec<Vec<f32>>>;
+   let mut out_arrays: Vec<Vec<Vec<f32>>>;
+   if options.numa_first_touch {
+      in_arrays = (0..narrays).map(|_| first_touch_array(&options)).collect();
+      out_arrays = (0..narrays).map(|_| first_touch_array(&options)).collect();
+   } else {
+      in_arrays = vec![vec![vec![0.0f32; nx]; ny]; narrays];
+      out_arrays = vec![vec![vec![0.0f32; nx]; ny]; narrays];
+   }
+   if narrays > 1 {
+      let bytes = 2 * narrays * nx * ny * std::mem::size_of::<f32>();
+      println!("Using {} array pairs, total working set {} bytes ({:.2} MB)",
+                                narrays,bytes,bytes as f64 / (1024.0 * 1024.0));
+   }
 
-   //  We set the elements of the input array to some set of values - it doesn't
-   //  matter what, just some values we can use to check the array manipulation
-   //  on. This uses the sum of the row and column indices in descending order.
-   //  We don't need to initialise the output array.
+   //  We set the elements of the input arrays to some set of values - it
+   //  doesn't matter what, just some values we can use to check the array
+   //  manipulation on. This uses the sum of the row and column indices in
+   //  descending order. We don't need to initialise the output arrays.
 
-   for iy in 0..ny {
-      for ix in 0..nx {
-         in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+   for in_array in in_arrays.iter_mut() {
+      for iy in 0..ny {
+         for ix in 0..nx {
+            in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+         }
       }
    }
 
-   //  Repeat the call to the manipulating subroutine.
+   //  The branchy kernel needs the values in no particular order, or the
+   //  branch it makes on each value is too easy for the processor to predict,
+   //  so the same range of values is scrambled for it.
 
-   for _irpt in 1..=nrpt {
-      crssub::csub (&in_array,nx,ny,&mut out_array);
+   if options.kernel_name == "branchy" {
+      for in_array in in_arrays.iter_mut() {
+         for iy in 0..ny {
+            for ix in 0..nx {
+               in_array[iy][ix] = scrambled_value(nx,ny,ix,iy);
+            }
+         }
+      }
    }
 
-   //  Check that we got the expected results.
+   //  Or they can be random, which gives the compiler no chance of knowing
+   //  anything about them in advance. A single generator is used for all the
+   //  arrays, so each is different.
 
-   'check_loop :
-   for iy in 0..ny {
-      for ix in 0..nx {
-         if out_array[iy][ix] != (in_array[iy][ix] + (ix + iy) as f32) {
-            println! ("Error {} {} {} {}",
-                           ix,iy,out_array[iy][ix],in_array[iy][ix]);
-            break 'check_loop;
+   if options.seed_per_repeat && options.seed.is_none() {
+      options.seed = Some(1);
+   }
+   if let Some(seed) = options.seed {
+      let mut random = crsrandom::Random::new(seed);
+      for in_array in in_arrays.iter_mut() {
+         for row in in_array.iter_mut() {
+            for value in row.iter_mut() {
+               *value = random.next_f32();
+            }
+         }
+      }
+   }
+
+   //  Alternatively, the input values can be read from a file, for example
+   //  real image data. This replaces the generated values in every input
+   //  array.
+
+   //  A file with more values than needed is allowed, and only the start of
+   //  it is read, but one with too few is an error.
+
+   if !options.input_file.is_empty() {
+      let (values,total) =
+          match crsfile::read_f32_file_start(&options.input_file,nx * ny,options.endian) {
+         Ok(result) => result,
+         Err(error) => {
+            println!("Unable to read {}: {}",options.input_file,error);
+            process::exit(1);
+         }
+      };
+      if total < nx * ny {
+         println!("{} holds {} values, but the arrays need {}",
+                                       options.input_file,total,nx * ny);
+         process::exit(1);
+      }
+      if total > nx * ny {
+         println!("Warning: {} holds {} values, using the first {} and ignoring \
+                   the other {}",options.input_file,total,nx * ny,total - nx * ny);
+      }
+      for in_array in in_arrays.iter_mut() {
+         for iy in 0..ny {
+            in_array[iy][..nx].copy_from_slice(&values[iy * nx..(iy + 1) * nx]);
+         }
+      }
+   }
+
+   //  The branchy kernel uses the median of the input values as its threshold,
+   //  so the branch goes each way about half the time.
+
+   if options.kernel_name == "branchy" && nx > 0 && ny > 0 {
+      let mut values: Vec<f32> = in_arrays[0].iter().flatten().cloned().collect();
+      values.sort_by(|a,b| a.total_cmp(b));
+      options.threshold = values[values.len() / 2];
+   }
+
+   //  The row header timing only reads the input array, and doesn't call
+   //  any version of csub().
+
+   if options.row_headers {
+      time_row_headers(&options,&in_arrays[0]);
+      return;
+   }
+
+   //  Run the test. Each version of csub() is passed to the same generic
+   //  routine, which is compiled separately for each of them. If there is
+   //  a time limit, the watchdog ends the program if it is exceeded, and
+   //  is stopped when it goes out of scope once the test is over.
+
+   let coords = if options.kernel_name == "sparse" {
+      sparse_coords(&options)
+   } else {
+      Vec::new()
+   };
+   let watchdog = Watchdog::start(options.timeout,true);
+   let profile = if options.profile {
+      let profile = crsprofile::start(PROFILE_INTERVAL_MICROS);
+      if profile.is_none() {
+         println!("--profile is only supported on Linux on x86_64, ignored");
+      }
+      profile
+   } else {
+      None
+   };
+   let nanosecs = match options.kernel_name.as_str() {
+      "csub" => run(crssub::csub,&options,&mut in_arrays,&mut out_arrays),
+      "iter" => run(crssub_iter::csub,&options,&mut in_arrays,&mut out_arrays),
+      "unsafe" => run(crssub_unsafe::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays),
+      #[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+      "prefetch" => run(crssub_prefetch::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays),
+      "threads" => {
+         let nthreads = options.nthreads;
+         println!("Using {} threads",nthreads);
+         let nanosecs =
+            run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_threads::csub(input_array,nx,ny,output_array,nthreads),
+                                      &options,&mut in_arrays,&mut out_arrays);
+         if options.scaling {
+            report_scaling(&options,&in_arrays[0]);
+         }
+         nanosecs
+      }
+      "falseshare" => {
+         let nthreads = options.nthreads;
+         println!("Using {} threads",nthreads);
+         let falseshare = |input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_falseshare::csub(input_array,nx,ny,output_array,nthreads);
+         let nanosecs = run(falseshare,&options,&mut in_arrays,&mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("falseshare",&falseshare),
+               ("threads",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  crssub_threads::csub(input_array,nx,ny,output_array,nthreads))]);
+         nanosecs
+      }
+      "dual" => {
+         let mut second_arrays = out_arrays.clone();
+         run_dual(&options,&in_arrays,&mut out_arrays,&mut second_arrays)
+      }
+      "branchy" => {
+         let threshold = options.threshold;
+         let branchy = |input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_branchy::csub(input_array,nx,ny,output_array,threshold);
+         let nanosecs = run(branchy,&options,&mut in_arrays,&mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("branchy",&branchy),("csub",&crssub::csub)]);
+         nanosecs
+      }
+      "twopass" => run(crssub_twopass::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays),
+      "memcpy" => run(crssub_memcpy::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays),
+      "div" => run(crssub_div::csub,&options,&mut in_arrays,&mut out_arrays),
+      "diagonal" => {
+         let nanosecs = run(crssub_diagonal::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("diagonal",&crssub_diagonal::csub),
+                                                          ("csub",&crssub::csub)]);
+         nanosecs
+      }
+      "morton" => {
+         let nanosecs = run(crssub_morton::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("morton",&crssub_morton::csub),
+               ("csub",&crssub::csub),("columns",&crssub_morton::csub_columns)]);
+         nanosecs
+      }
+      "itercount" => {
+         let nanosecs = run(crssub_itercount::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("itercount",&crssub_itercount::csub),
+                                                          ("iter",&crssub_iter::csub)]);
+         nanosecs
+      }
+      "op" => {
+         let op = options.op;
+         run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_op::csub(input_array,nx,ny,output_array,op),
+                                      &options,&mut in_arrays,&mut out_arrays)
+      }
+      "fma" => {
+         let nanosecs = run(crssub_fma::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("fma",&crssub_fma::csub),
+                                                          ("csub",&crssub::csub)]);
+         nanosecs
+      }
+      "vector" => {
+         let nanosecs = run(crssub_vector::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_vector(&options);
+         nanosecs
+      }
+      "auto" => {
+         if !options.auto_kernel.is_empty() {
+            match crssub_auto::Variant::from_name(&options.auto_kernel) {
+               Some(variant) => {
+                  crssub_auto::force(variant);
+               }
+               None => println!("Unknown --auto-kernel {}, ignored",options.auto_kernel),
+            }
+         }
+
+         //  The first call makes the choice, so it is timed separately, to
+         //  show what the calibration costs.
+
+         let start = Instant::now();
+         crssub_auto::csub(&in_arrays[0],nx,ny,&mut out_arrays[0]);
+         let first_nanosecs = start.elapsed().as_nanos() as f64;
+         if let Some(variant) = crssub_auto::chosen() {
+            println!("Auto kernel using {}, first call took {:.6} sec",
+                                        variant.name(),first_nanosecs * 1.0e-9);
+         }
+         run(crssub_auto::csub,&options,&mut in_arrays,&mut out_arrays)
+      }
+      "sparse" => {
+         println!("Updating {} of the {} elements",coords.len(),nx * ny);
+         let nanosecs =
+            run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_sparse::csub(input_array,nx,ny,output_array,&coords),
+                                      &options,&mut in_arrays,&mut out_arrays);
+         if !coords.is_empty() {
+            compare_sparse(&options,&in_arrays[0],&coords);
+         }
+         nanosecs
+      }
+      "prefix" => {
+         let nanosecs = run(crssub_prefix::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("prefix",&crssub_prefix::csub),
+                                                          ("csub",&crssub::csub)]);
+         nanosecs
+      }
+      "vertical" => {
+         let nanosecs = run(crssub_simd_vertical::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         if crssub_simd_vertical::uses_intrinsics() {
+            println! ("Vertical kernel using AVX2 intrinsics");
+         } else {
+            println! ("Vertical kernel using the portable version, no AVX2");
+         }
+         compare_kernels(&options,&in_arrays[0],&[("vertical",&crssub_simd_vertical::csub),
+               ("csub",&crssub::csub),("unsafe",&crssub_unsafe::csub)]);
+         nanosecs
+      }
+      "simd" => {
+         let tail = options.tail;
+         let nanosecs = run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+               crssub_simd::csub(input_array,nx,ny,output_array,tail),
+                                      &options,&mut in_arrays,&mut out_arrays);
+         compare_simd_tail(&options,&in_arrays[0]);
+         nanosecs
+      }
+      "hint" => {
+         let nanosecs = run(crssub_hint::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("hint",&crssub_hint::csub),
+               ("csub",&crssub::csub),("unsafe",&crssub_unsafe::csub)]);
+         nanosecs
+      }
+      "rowptr" => {
+         let nanosecs = run(crssub_rowptr::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_kernels(&options,&in_arrays[0],&[("rowptr",&crssub_rowptr::csub),
+                                                          ("unsafe",&crssub_unsafe::csub)]);
+         nanosecs
+      }
+      "recursive" => {
+         let nanosecs = run(crssub_recursive::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays);
+         compare_recursive(&options,&in_arrays[0]);
+         nanosecs
+      }
+      "lenbound" => run(crssub_lenbound::csub,&options,&mut in_arrays,
+                                                               &mut out_arrays),
+      "checksum" => {
+
+         //  The checksum version returns a value, which is accumulated
+         //  over all the calls and printed, so the optimiser cannot decide
+         //  the calls are unnecessary. The checksum for the last call is
+         //  also kept, so it can be checked against the output array.
+
+         let total = Cell::new(0.0f64);
+         let last = Cell::new(0.0f64);
+         let nanosecs = run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>| {
+               let checksum = crssub_checksum::csub(input_array,nx,ny,output_array);
+               total.set(total.get() + checksum);
+               last.set(checksum);
+            },&options,&mut in_arrays,&mut out_arrays);
+         println!("Checksum total over all calls {}",total.get());
+         if nrpt > 0 {
+            let iarray = (nrpt - 1) % narrays;
+            let expected = sum_array(&out_arrays[iarray]);
+            if last.get() != expected {
+               println!("Error: final checksum {}, expected {}",last.get(),expected);
+            }
+         }
+         nanosecs
+      }
+      "fold" => {
+
+         //  As for the checksum version.
+
+         let total = Cell::new(0.0f64);
+         let last = Cell::new(0.0f64);
+         let nanosecs = run(|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>| {
+               let checksum = crssub_fold::csub(input_array,nx,ny,output_array);
+               total.set(total.get() + checksum);
+               last.set(checksum);
+            },&options,&mut in_arrays,&mut out_arrays);
+         println!("Checksum total over all calls {}",total.get());
+         if nrpt > 0 {
+            let iarray = (nrpt - 1) % narrays;
+            let expected = sum_array(&out_arrays[iarray]);
+            if last.get() != expected {
+               println!("Error: final checksum {}, expected {}",last.get(),expected);
+            }
          }
+         compare_fold(&options,&in_arrays[0]);
+         nanosecs
+      }
+      "nz" => {
+
+         //  This is the one place the dimensions have to be shown not to be
+         //  zero, and the program stops here, before any calls, if they are.
+
+         let (Some(nx_nz),Some(ny_nz)) = (NonZeroUsize::new(nx),NonZeroUsize::new(ny)) else {
+            println!("The nz kernel needs non-zero dimensions, not {} rows of {} columns",
+                                                                          ny,nx);
+            process::exit(EMPTY_ARRAY_STATUS);
+         };
+         run(|input_array: &Vec<Vec<f32>>,_nx,_ny,output_array: &mut Vec<Vec<f32>>| {
+               crssub::csub_nz(input_array,nx_nz,ny_nz,output_array);
+            },&options,&mut in_arrays,&mut out_arrays)
+      }
+      #[cfg(not(all(target_arch = "x86_64",feature = "prefetch")))]
+      "prefetch" => {
+         println!("The prefetch kernel needs an x86_64 build with the 'prefetch' feature, using csub");
+         options.kernel_name = String::from("csub");
+         run(crssub::csub,&options,&mut in_arrays,&mut out_arrays)
+      }
+      _ => {
+         println!("Unknown kernel {}, using csub",options.kernel_name);
+         options.kernel_name = String::from("csub");
+         run(crssub::csub,&options,&mut in_arrays,&mut out_arrays)
+      }
+   };
+   if let Some(profile) = profile {
+      report_profile(profile);
+   }
+
+   drop(watchdog);
+   check_plausible_time(&options,nanosecs);
+   if !options.baseline_file.is_empty() {
+      report_baseline(&options,nanosecs);
+   }
+
+   //  With either dimension zero, the checks below would find nothing wrong,
+   //  having nothing to look at, which would look like success. This is
+   //  reported instead, once any result has been written.
+
+   if nx == 0 || ny == 0 {
+      write_record(&options,nanosecs);
+      println!("Empty array, nothing to verify");
+      process::exit(EMPTY_ARRAY_STATUS);
+   }
+
+   //  Check that we got the expected results. If there were fewer repeats
+   //  than array pairs, only the pairs that were actually used can be checked.
+
+   for (in_array,out_array) in
+             in_arrays.iter().zip(out_arrays.iter()).take(nrpt.min(narrays)) {
+      if !check_output(&options,&options.kernel_name,in_array,out_array) {
+         break;
       }
    }
+   let mut sum_ok = true;
+   if options.reference_sum {
+      sum_ok = check_reference_sum(&options,&out_arrays[..nrpt.min(narrays)]);
+   }
+
+   //  If requested, record the result of the test.
 
+   write_record(&options,nanosecs);
+
+   //  If requested, write out the output array - the first of them if there
+   //  are more than one.
+
+   if !options.output_file.is_empty() {
+      if let Err(error) =
+                crsfile::write_f32_file(&options.output_file,&out_arrays[0],
+                                                               options.endian) {
+         println!("Unable to write {}: {}",options.output_file,error);
+      }
+   }
+   if !options.pgm_file.is_empty() {
+      if let Err(error) = crsfile::write_pgm_file(&options.pgm_file,&out_arrays[0]) {
+         println!("Unable to write {}: {}",options.pgm_file,error);
+      }
+   }
+   if !sum_ok {
+      process::exit(1);
+   }
 }
 
-/*  ----------------------------------------------------------------------------
+//  ----------------------------------------------------------------------------
+//
+//                   C h e c k  P l a u s i b l e  T i m e
+//
+//   Warns if the time taken by the normal test is less than MIN_NS_PER_ELEMENT
+//   for each element of each call, which can only mean the compiler has
+//   seen that the results of the calls aren't used, and removed them. The
+//   sparse kernel only writes some of the elements, so isn't checked, and
+//   nor is a test that has no elements at all.
 
-                  P r o g r a m m i n g   N o t e s
+fn check_plausible_time (options: &Options,nanosecs: f64) {
+   let elements = options.nrpt * options.nx * options.ny;
+   if elements == 0 || options.kernel_name == "sparse" {
+      return;
+   }
+   let ns_per_element = nanosecs / elements as f64;
+   if ns_per_element < MIN_NS_PER_ELEMENT {
+      println!("WARNING: {:.6} ns per element is faster than any machine can manage",
+                                                                  ns_per_element);
+      println!("   (the floor is {} ns). The loop may have been optimised away.",
+                                                              MIN_NS_PER_ELEMENT);
+      println!("   Try --repeat-pattern, or pass the arrays through");
+      println!("   std::hint::black_box() as the 'markers' feature does.");
+   }
+}
 
-   o The code checks that the command line arguments are valid numbers, but
-     doesn't check that they're not zero. It is only a test routine. It only
-     checks they're valid numbers because I was trying to understand the
-     way to do that, using match to check the parse() result.
+//  ----------------------------------------------------------------------------
+//
+//                            P a r s e  S i z e
+//
+//   Parses the value of --size, which should be two whole numbers greater
+//   than zero separated by an 'x', the width - the number of columns -
+//   first, as in 2000x10. Returns the width and height, or a message saying
+//   what was wrong.
 
-   o The code can be made to run faster by using a 1D array and doing the
-     index calculations in the code, but that seems to defeat the point of
-     this test. (Indeed, I've seen comments that suggest this as the best
-     way to speed up 2D array access in Rust. Perhaps this will change as
-     the compiler matures, as it did with Swift.)
+fn parse_size (text: &str) -> Result<(usize,usize),String> {
+   let parts = text.split_once(['x','X']);
+   if let Some((width,height)) = parts {
+      if let (Ok(width),Ok(height)) = (width.parse::<usize>(),height.parse::<usize>()) {
+         if width > 0 && height > 0 {
+            return Ok((width,height));
+         }
+      }
+   }
+   Err(format!("Invalid --size={}: expected the width and height as two whole \
+                numbers greater than zero, as in --size=2000x10",text))
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           P a r s e  C o u n t
+//
+//   Parses a count given on the command line. As well as a plain integer,
+//   this accepts underscores used as separators, as in 1_000_000, and
+//   scientific notation, as in 1e6 or 2.5E5, so long as the value is a
+//   non-negative whole number that fits in a usize. Anything else returns
+//   None, and the caller can fall back on its default.
+
+fn parse_count (text: &str) -> Option<usize> {
+   let digits: String = text.chars().filter(|&c| c != '_').collect();
+   if let Ok(number) = digits.parse::<usize>() {
+      return Some(number);
+   }
+   match digits.parse::<f64>() {
+      Ok(value) if value >= 0.0 && value.fract() == 0.0
+                                       && value < usize::MAX as f64 => {
+         Some(value as usize)
+      }
+      _ => None,
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          R e p o r t  C a c h e s
+//
+//   Prints the size of each level of data cache, and whether the working set
+//   of the test - the total size of all the input and output arrays - fits
+//   into it. This ignores the small amount of memory used by the vectors
+//   holding the rows, and anything else the program uses.
+
+fn report_caches (options: &Options) {
+   let caches = crsos::cache_sizes();
+   if caches.is_empty() {
+      println! ("Cache sizes not available");
+      return;
+   }
+   let bytes = 2 * options.narrays * options.nx * options.ny * std::mem::size_of::<f32>();
+   let mut fits: Vec<String> = Vec::new();
+   for (level,size) in caches.iter() {
+      let verdict = if bytes <= *size { "fits" } else { "doesn't fit" };
+      fits.push(format!("L{} {} KB ({})",level,size / 1024,verdict));
+   }
+   println! ("Working set {} KB; data caches {}",bytes / 1024,fits.join(", "));
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                              T a k e  L o c k
+//
+//   Implements --exclusive. Opens the lock file, creating it if necessary,
+//   and takes an exclusive lock on it, waiting for it if wait is set, and
+//   returns the open file, which holds the lock until it is closed. If the
+//   lock is held by another program and wait isn't set, or anything goes
+//   wrong, returns a message saying why.
+
+fn take_lock (wait: bool) -> Result<fs::File,String> {
+   let path = env::temp_dir().join("crs_bench.lock");
+   let file = fs::OpenOptions::new().create(true).truncate(false).write(true)
+                                                                  .open(&path)
+              .map_err(|error| format!("Unable to open {}: {}",path.display(),error))?;
+   match file.try_lock() {
+      Ok(()) => return Ok(file),
+      Err(fs::TryLockError::WouldBlock) => {}
+      Err(fs::TryLockError::Error(error)) => {
+         return Err(format!("Unable to lock {}: {}",path.display(),error));
+      }
+   }
+   if !wait {
+      return Err(format!("Another test holds the lock on {}, not running",
+                                                               path.display()));
+   }
+   println! ("Waiting for another test to release the lock on {}",path.display());
+   file.lock().map_err(|error| format!("Unable to lock {}: {}",path.display(),error))?;
+   Ok(file)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                                   R u n
+//
+//   Runs the test for the supplied version of csub(), in whatever way the
+//   options specify. This is generic in the function passed, so each version
+//   of csub() gets its own copy of this code and can be inlined into it, just
+//   as if it had been called directly from the main routine. Returns the
+//   total time taken by the calls, in nanoseconds.
+
+fn run<F> (kernel: F,options: &Options,
+             in_arrays: &mut [Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>]) -> f64
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   if options.page_faults {
+      count_page_faults(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.burn_in > 0 {
+      burn_in(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.max_warmups > 0 {
+      warm_up(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.affinity_sweep {
+      affinity_sweep(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.sibling_load {
+      sibling_load(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.runs > 1 {
+      measure_drift(&kernel,options,in_arrays,out_arrays);
+   }
+   if options.latency && !options.percentiles && options.raw_timings.is_empty()
+                                               && options.bench_json.is_empty() {
+
+      //  Only the running statistics are needed, and not every time.
+
+      let accumulator = accumulate_each_call(kernel,options,in_arrays,out_arrays);
+      if accumulator.count() > 0 {
+         print_running_summary("Latency of isolated calls",&accumulator);
+      }
+      accumulator.summary().mean * accumulator.count() as f64
+   } else if options.latency || !options.raw_timings.is_empty()
+                             || !options.bench_json.is_empty() {
+
+      //  Each call is to be timed individually. The raw timings, if wanted,
+      //  are written out only once all the calls have been made, so the file
+      //  I/O has no effect on the timings themselves.
+
+      let mut times = time_each_call(kernel,options,in_arrays,out_arrays);
+      if !options.raw_timings.is_empty() {
+         if let Err(error) =
+                     crsfile::write_timings(&options.raw_timings,&times) {
+            println!("Unable to write {}: {}",options.raw_timings,error);
+         }
+      }
+      let nanosecs = times.iter().sum();
+      if !options.bench_json.is_empty() {
+         if times.is_empty() {
+            println!("No calls were timed, so {} is not written",options.bench_json);
+         } else {
+            let summary = crstime::summarise(&mut times);
+            match crsresult::write_bench_json(&options.bench_json,&bench_command(),&summary) {
+               Ok(()) => println!("Benchmark results written to {}",options.bench_json),
+               Err(error) => println!("Unable to write {}: {}",options.bench_json,error),
+            }
+         }
+      }
+      if !times.is_empty() {
+         if options.latency {
+            print_summary("Latency of isolated calls",&mut times);
+         } else {
+            print_summary("Time for each call",&mut times);
+         }
+      }
+      nanosecs
+   } else if options.cycles {
+      count_cycles(kernel,options,in_arrays,out_arrays)
+   } else {
+      let start = Instant::now();
+      call_repeatedly(kernel,options,in_arrays,out_arrays);
+      let mut nanosecs = start.elapsed().as_nanos() as f64;
+      if options.seed_per_repeat {
+
+         //  Time the same refills on their own, using copies of the input
+         //  arrays, and take that time off the total.
+
+         let mut refill_arrays = in_arrays.to_vec();
+         let start = Instant::now();
+         for irpt in 1..=options.nrpt {
+            let iarray = (irpt - 1) % refill_arrays.len();
+            refill_row(options,irpt,&mut refill_arrays[iarray]);
+         }
+         let refill_nanosecs = start.elapsed().as_nanos() as f64;
+         println! ("Refilling rows took {:.6} sec, subtracted from the time",
+                                                     refill_nanosecs * 1.0e-9);
+         nanosecs = (nanosecs - refill_nanosecs).max(0.0);
+      }
+      if options.roofline > 0.0 {
+         report_roofline(options,nanosecs);
+      }
+      if options.vs_memcpy {
+
+         //  The copy uses separate copies of the arrays, so the results of
+         //  the test itself can still be checked. (With --repeat-pattern,
+         //  call_repeatedly() changes the input arrays as it goes.)
+
+         let mut copy_in_arrays = in_arrays.to_vec();
+         let mut copy_arrays = out_arrays.to_vec();
+         let start = Instant::now();
+         call_repeatedly(crssub_memcpy::csub,options,&mut copy_in_arrays,
+                                                              &mut copy_arrays);
+         let copy_nanosecs = start.elapsed().as_nanos() as f64;
+         println! ("Time {:.6} sec, memcpy time {:.6} sec, ratio {:.3}",
+                   nanosecs * 1.0e-9,copy_nanosecs * 1.0e-9,
+                                                   nanosecs / copy_nanosecs);
+      }
+      nanosecs
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                              W a t c h d o g
+//
+//   A Watchdog implements the --timeout option. Starting one starts a thread
+//   that waits on a channel for up to the time limit. Dropping the Watchdog
+//   drops the sending end of the channel, which wakes the thread up and it
+//   simply ends. If the time limit is reached first, the thread either ends
+//   the program or sets TIMED_OUT, for code that checks it. Either way, the
+//   thread is asleep until then, so it has no effect on the timings.
+
+struct Watchdog {
+   _sender: mpsc::Sender<()>,
+}
+
+impl Watchdog {
+   fn start (seconds: f64,exit: bool) -> Option<Watchdog> {
+      if seconds <= 0.0 {
+         return None;
+      }
+      TIMED_OUT.store(false,atomic::Ordering::Relaxed);
+      let (sender,receiver) = mpsc::channel::<()>();
+      thread::spawn(move || {
+         if let Err(mpsc::RecvTimeoutError::Timeout) =
+                          receiver.recv_timeout(Duration::from_secs_f64(seconds)) {
+            if exit {
+               println!("Test not finished after {} seconds, exiting",seconds);
+               process::exit(1);
+            }
+            TIMED_OUT.store(true,atomic::Ordering::Relaxed);
+         }
+      });
+      Some(Watchdog { _sender: sender })
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                               W a r m  U p
+//
+//   Makes warmup calls to the supplied version of csub(), using each pair of
+//   arrays in turn, timing each call, and stops once the time for a call is
+//   within WARMUP_TOLERANCE of the time for the previous call, or once the
+//   maximum number of warmup calls set by --warmup have been made. A fixed
+//   number of warmup calls can be too few if the first calls are slowed by
+//   page faults as a large new array is touched for the first time, and
+//   stopping once the times settle handles this whatever the array size.
+
+fn warm_up<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let narrays = in_arrays.len();
+   let mut previous = 0.0;
+   for iwarm in 0..options.max_warmups {
+      let iarray = iwarm % narrays;
+      let start = Instant::now();
+      kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+      let nanosecs = start.elapsed().as_nanos() as f64;
+      if iwarm > 0 && (nanosecs - previous).abs() <= WARMUP_TOLERANCE * previous {
+         println! ("Timings stable after {} warmup calls",iwarm + 1);
+         return;
+      }
+      previous = nanosecs;
+   }
+   println! ("Timings not stable after {} warmup calls",options.max_warmups);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                               B u r n  I n
+//
+//   Implements --burn-in. Calls the supplied version of csub() over and over,
+//   using each pair of arrays in turn, until the time given has passed, and
+//   reports how many calls were made and how long the first and last took.
+//   If the processor's clock speed went up during the burn-in, the last call
+//   should be quicker than the first.
+
+fn burn_in<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let narrays = in_arrays.len();
+   let duration = Duration::from_millis(options.burn_in);
+   let (mut first,mut last) = (0.0,0.0);
+   let mut ncalls = 0;
+   let start = Instant::now();
+   while start.elapsed() < duration {
+      let iarray = ncalls % narrays;
+      let call_start = Instant::now();
+      kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+      last = call_start.elapsed().as_nanos() as f64;
+      if ncalls == 0 {
+         first = last;
+      }
+      ncalls += 1;
+   }
+   println! ("Burn-in of {} ms made {} calls, first took {:.3} microsec, last {:.3} microsec",
+              options.burn_in,ncalls,first * 1.0e-3,last * 1.0e-3);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      C o u n t  P a g e  F a u l t s
+//
+//   Makes two calls to the supplied version of csub(), using the first pair
+//   of arrays, and prints the time taken by each and the number of minor and
+//   major page faults that happened during it. This is called before any
+//   other calls, so the first call is the first time the output array (and,
+//   unless the input values were set from the program, the input array) is
+//   touched.
+
+fn count_page_faults<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   if crsos::page_faults().is_none() {
+      println! ("--page-faults is only supported on Linux, ignored");
+      return;
+   }
+   for call in ["First","Second"] {
+      let (minor_before,major_before) = crsos::page_faults().unwrap();
+      let start = Instant::now();
+      kernel (&in_arrays[0],options.nx,options.ny,&mut out_arrays[0]);
+      let nanosecs = start.elapsed().as_nanos() as f64;
+      let (minor_after,major_after) = crsos::page_faults().unwrap();
+      println! ("{} call: {:.3} microseconds, {} minor and {} major page faults",
+                call,nanosecs * 1.0e-3,minor_after - minor_before,
+                                                  major_after - major_before);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         A f f i n i t y  S w e e p
+//
+//   Pins the calling thread to each of the processors it is allowed to run
+//   on in turn, times nrpt calls to the supplied version of csub() on each,
+//   and prints the median time per call for each processor, marking the
+//   fastest and slowest. The original affinity is restored afterwards, so
+//   the normal test that follows isn't pinned. The first call on each
+//   processor is made untimed, so the caches local to it are warmed up.
+
+fn affinity_sweep<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let original = match crsos::get_affinity() {
+      Some(mask) => mask,
+      None => {
+         println! ("--affinity-sweep is only supported on Linux, ignored");
+         return;
+      }
+   };
+   let narrays = in_arrays.len();
+   let mut medians: Vec<(usize,f64)> = Vec::new();
+   for cpu in original.cpus() {
+      if !crsos::set_affinity(&crsos::CpuMask::single(cpu)) {
+         println! ("Unable to run on processor {}, skipped",cpu);
+         continue;
+      }
+      kernel (&in_arrays[0],options.nx,options.ny,&mut out_arrays[0]);
+      let mut times: Vec<f64> = Vec::with_capacity(options.nrpt);
+      for irpt in 0..options.nrpt {
+         let iarray = irpt % narrays;
+         let start = Instant::now();
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         times.push(start.elapsed().as_nanos() as f64);
+      }
+      if !times.is_empty() {
+         medians.push((cpu,crstime::summarise(&mut times).median));
+      }
+   }
+   crsos::set_affinity(&original);
+   if medians.is_empty() {
+      return;
+   }
+   let fastest = medians.iter().map(|&(_,median)| median).fold(f64::INFINITY,f64::min);
+   let slowest = medians.iter().map(|&(_,median)| median).fold(0.0,f64::max);
+   println! ("Median time per call on each processor, in microseconds:");
+   for (cpu,median) in medians.iter() {
+      let mark = if medians.len() < 2 {
+         ""
+      } else if *median == fastest {
+         "  fastest"
+      } else if *median == slowest {
+         "  slowest"
+      } else {
+         ""
+      };
+      println! ("{:>6} {:>12.3}{}",cpu,median * 1.0e-3,mark);
+   }
+   if medians.len() > 1 {
+      println! ("Slowest processor takes {:.1}% longer than the fastest",
+                                            100.0 * (slowest - fastest) / fastest);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          R e p o r t  P r o f i l e
+//
+//   Implements --profile. Stops the sampling and prints the PROFILE_FUNCTIONS
+//   functions with the most samples, with the percentage of the samples in
+//   each, and the percentage in all the rest together. A version of csub()
+//   is usually inlined into the generic run() and call_repeatedly(), so its
+//   time is normally reported under their names rather than its own.
+
+fn report_profile (profile: crsprofile::Profile) {
+   let (count,samples) = crsprofile::stop(profile);
+   if samples.is_empty() {
+      println!("Profile: no samples were taken");
+      return;
+   }
+   println!("Profile, {} samples, requested every {} microseconds of processor time:",
+                                                   count,PROFILE_INTERVAL_MICROS);
+   if count > samples.len() {
+      println!("   (only the first {} recorded)",samples.len());
+   }
+   let total = samples.len() as f64;
+   let histogram = crsprofile::histogram(&samples);
+   for (name,number) in histogram.iter().take(PROFILE_FUNCTIONS) {
+      println!("   {:>6.2}%  {}",100.0 * *number as f64 / total,name);
+   }
+   let rest: usize = histogram.iter().skip(PROFILE_FUNCTIONS).map(|(_name,number)| number).sum();
+   if rest > 0 {
+      println!("   {:>6.2}%  (the other {} functions)",100.0 * rest as f64 / total,
+                                                   histogram.len() - PROFILE_FUNCTIONS);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          M e a s u r e  D r i f t
+//
+//   Implements --runs. Makes the number of complete measurements asked for,
+//   each made up of a warmup and then nrpt calls to the supplied version of
+//   csub() timed one by one, with a pause of RUN_SPACING_MILLIS before each
+//   after the first. Prints the median and the coefficient of variation of
+//   the times for each run, then the drift between the runs' medians and
+//   the average variation within a run, and warns if the drift is more than
+//   CV_THRESHOLD, since then the result of any single run depends on when
+//   it was made.
+
+fn measure_drift<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   if options.nrpt == 0 {
+      return;
+   }
+   let narrays = in_arrays.len();
+   let mut results: Vec<(f64,f64)> = Vec::with_capacity(options.runs);
+   for irun in 0..options.runs {
+      if irun > 0 {
+         std::thread::sleep(Duration::from_millis(RUN_SPACING_MILLIS));
+      }
+      if options.max_warmups > 0 {
+         warm_up(kernel,options,in_arrays,out_arrays);
+      } else {
+         for iwarm in 0..RUN_WARMUPS {
+            let iarray = iwarm % narrays;
+            kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         }
+      }
+      let mut times: Vec<f64> = Vec::with_capacity(options.nrpt);
+      for irpt in 0..options.nrpt {
+         let iarray = irpt % narrays;
+         let start = Instant::now();
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         times.push(start.elapsed().as_nanos() as f64);
+      }
+      let summary = crstime::summarise(&mut times);
+      results.push((summary.median,summary.coefficient_of_variation()));
+   }
+   println! ("Median time per call for each run, in microseconds:");
+   for (irun,(median,cv)) in results.iter().enumerate() {
+      println! ("{:>6} {:>12.3}   variation {:.2}%",irun + 1,median * 1.0e-3,100.0 * cv);
+   }
+   let fastest = results.iter().map(|&(median,_)| median).fold(f64::INFINITY,f64::min);
+   let slowest = results.iter().map(|&(median,_)| median).fold(0.0,f64::max);
+   let drift = (slowest - fastest) / fastest;
+   let jitter = results.iter().map(|&(_,cv)| cv).sum::<f64>() / results.len() as f64;
+   println! ("Drift between runs {:.2}%, average variation within a run {:.2}%",
+                                                   100.0 * drift,100.0 * jitter);
+   if drift > CV_THRESHOLD {
+      println! ("   RUN-TO-RUN DRIFT: medians differ by more than {:.0}%, so a single",
+                                                          100.0 * CV_THRESHOLD);
+      println! ("   run may not be representative. Look for throttling or other load.");
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           S i b l i n g  L o a d
+//
+//   Implements --sibling-load. Pins the calling thread to the first processor
+//   it is allowed to run on and times nrpt calls to the supplied version of
+//   csub(), then does the same again with a second thread pinned to an SMT
+//   sibling of that processor, busy updating a buffer much bigger than the
+//   level 2 cache. Prints both times and their ratio. The original affinity
+//   is restored afterwards, so the normal test that follows isn't pinned.
+
+fn sibling_load<F> (kernel: &F,options: &Options,
+               in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let original = match crsos::get_affinity() {
+      Some(mask) => mask,
+      None => {
+         println! ("--sibling-load is only supported on Linux, ignored");
+         return;
+      }
+   };
+   let cpu = match original.cpus().first() {
+      Some(&cpu) => cpu,
+      None => return,
+   };
+   let sibling = match crsos::smt_siblings(cpu).first() {
+      Some(&sibling) => sibling,
+      None => {
+         println! ("Processor {} has no SMT sibling, --sibling-load skipped",cpu);
+         return;
+      }
+   };
+   if !crsos::set_affinity(&crsos::CpuMask::single(cpu)) {
+      println! ("Unable to run on processor {}, --sibling-load skipped",cpu);
+      return;
+   }
+
+   //  The first call is made untimed each time, as in affinity_sweep().
+
+   let narrays = in_arrays.len();
+   let mut time_calls = || {
+      kernel (&in_arrays[0],options.nx,options.ny,&mut out_arrays[0]);
+      let start = Instant::now();
+      for irpt in 0..options.nrpt {
+         let iarray = irpt % narrays;
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+      }
+      start.elapsed().as_nanos() as f64
+   };
+   let quiet_nanosecs = time_calls();
+   let stop = &atomic::AtomicBool::new(false);
+   let loaded_nanosecs = thread::scope(|scope| {
+      let (sender,receiver) = mpsc::channel();
+      let load = scope.spawn(move || {
+         let pinned = crsos::set_affinity(&crsos::CpuMask::single(sibling));
+         let _ = sender.send(pinned);
+         if !pinned {
+            return;
+         }
+         let mut buffer = vec![0u64; SIBLING_LOAD_BYTES / std::mem::size_of::<u64>()];
+         let nwords = buffer.len();
+         let mut index = 0;
+         while !stop.load(atomic::Ordering::Relaxed) {
+            for _iword in 0..1024 {
+               buffer[index] = buffer[index].wrapping_add(1);
+               index = (index + SIBLING_LOAD_STRIDE) % nwords;
+            }
+         }
+         std::hint::black_box(&buffer);
+      });
+      let pinned = receiver.recv().unwrap_or(false);
+      let nanosecs = if pinned { Some(time_calls()) } else { None };
+      stop.store(true,atomic::Ordering::Relaxed);
+      let _ = load.join();
+      nanosecs
+   });
+   crsos::set_affinity(&original);
+   match loaded_nanosecs {
+      Some(loaded_nanosecs) => {
+         println! ("Test on processor {}, load on its sibling, processor {}",cpu,sibling);
+         println! ("Time without load {:.6} sec, with load {:.6} sec, ratio {:.3}",
+                   quiet_nanosecs * 1.0e-9,loaded_nanosecs * 1.0e-9,
+                                              loaded_nanosecs / quiet_nanosecs);
+      }
+      None => println! ("Unable to run the load on processor {}, --sibling-load skipped",
+                                                                         sibling),
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        T i m e  R o w  H e a d e r s
+//
+//   Times nrpt passes over the array that read only the first element of
+//   each row, and nrpt passes that read every element, and prints the time
+//   per row for each. Reading array[iy][0] means loading the pointer to row
+//   iy from the outer vector and then loading the element it points to, so
+//   the first time is essentially the cost of getting to each row. The
+//   second loop adds each row into a row of column totals, so it reads each
+//   element once, as csub() does, in a way the compiler can vectorise. The
+//   array is passed through black_box() on each pass, so the compiler can't
+//   load the row pointers once and reuse them for every pass.
+
+fn time_row_headers (options: &Options,array: &[Vec<f32>]) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   if nrpt == 0 || nx == 0 || ny == 0 {
+      println! ("Row header timing needs at least one repeat, row and column");
+      return;
+   }
+   let mut total = 0.0f32;
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      let rows = std::hint::black_box(array);
+      for row in &rows[..ny] {
+         total += row[0];
+      }
+   }
+   let header_nanosecs = start.elapsed().as_nanos() as f64;
+   let mut column_totals = vec![0.0f32; nx];
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      let rows = std::hint::black_box(array);
+      for row in &rows[..ny] {
+         for (column_total,value) in column_totals.iter_mut().zip(&row[..nx]) {
+            *column_total += *value;
+         }
+      }
+   }
+   let full_nanosecs = start.elapsed().as_nanos() as f64;
+   std::hint::black_box(total);
+   std::hint::black_box(&column_totals);
+   let calls = (nrpt * ny) as f64;
+   println! ("First element only {:.3} ns per row, all elements {:.3} ns per row",
+                            header_nanosecs / calls,full_nanosecs / calls);
+   println! ("Getting to each row is {:.1}% of the time to read all of it",
+                                      100.0 * header_nanosecs / full_nanosecs);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         R e p o r t  S c a l i n g
+//
+//   Times nrpt calls to the threads version of csub() for 1, 2, 4... threads,
+//   up to and including the number set by --threads, and prints the time
+//   and the speedup relative to one thread for each. The output goes to a
+//   scratch array, so the results of the main test are unaffected, and the
+//   scratch array is cleared and checked for each number of threads.
+
+fn report_scaling (options: &Options,in_array: &Vec<Vec<f32>>) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let mut out_array = in_array.clone();
+   let mut counts = Vec::new();
+   let mut nthreads = 1;
+   while nthreads < options.nthreads {
+      counts.push(nthreads);
+      nthreads *= 2;
+   }
+   counts.push(options.nthreads);
+   println! ("{:>8} {:>12} {:>8}","Threads","Time (sec)","Speedup");
+   let mut single = 0.0;
+   for nthreads in counts {
+      for row in out_array.iter_mut() {
+         row.fill(0.0);
+      }
+      let start = Instant::now();
+      for _irpt in 1..=nrpt {
+         crssub_threads::csub(in_array,nx,ny,&mut out_array,nthreads);
+      }
+      let nanosecs = start.elapsed().as_nanos() as f64;
+      if nrpt > 0 && !check_output(options,"threads",in_array,&out_array) {
+         println! ("Incorrect results for {} threads",nthreads);
+      }
+      if nthreads == 1 {
+         single = nanosecs;
+      }
+      println! ("{:>8} {:>12.6} {:>8.2}{}",nthreads,nanosecs * 1.0e-9,single / nanosecs,
+                         if nthreads == 1 { "  (serial, on the calling thread)" } else { "" });
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       S c r a m b l e d  V a l u e
+//
+//   Returns the input value used for element [iy][ix] by the branchy kernel.
+//   This takes the same range of values as the normal descending pattern,
+//   from 0 up to nx + ny, but uses a multiplicative hash of the element
+//   number to pick the value, so that neighbouring elements have unrelated
+//   values.
+
+fn scrambled_value (nx: usize,ny: usize,ix: usize,iy: usize) -> f32 {
+   let mut hash = ((iy * nx + ix) as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+   hash ^= hash >> 32;
+   (hash % (nx + ny) as u64) as f32
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      C o m p a r e  S i m d  T a i l
+//
+//   First checks that the two ways the simd kernel in crssub_simd.rs can
+//   handle the ends of the rows give the same results as crssub.rs, for a few
+//   rows of every length from 1 to 17, and that neither writes past the end
+//   of a row. Then times the same number of calls with each, using
+//   compare_kernels(), and also prints the time per element for each.
+
+fn compare_simd_tail (options: &Options,in_array: &Vec<Vec<f32>>) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   if crssub_simd::uses_intrinsics() {
+      println! ("Simd kernel using AVX2 intrinsics, rows of {} leave a tail of {}",nx,nx % 8);
+   } else {
+      println! ("Simd kernel using the usual loops, no AVX2, so the tail makes no difference");
+   }
+
+   //  The rows are made longer than the width being tested, and set to a
+   //  value that can't be a result, so anything written past the width shows.
+
+   const CHECK_ROWS: usize = 3;
+   const MAX_CHECK_WIDTH: usize = 17;
+   const UNTOUCHED: f32 = -1.0;
+   let check_in: Vec<Vec<f32>> = (0..CHECK_ROWS).map(|iy|
+         (0..MAX_CHECK_WIDTH + 8).map(|ix| (iy * 100 + ix) as f32 * 0.5).collect()).collect();
+   let mut errors = 0;
+   for width in 1..=MAX_CHECK_WIDTH {
+      let mut expected = vec![vec![UNTOUCHED; MAX_CHECK_WIDTH + 8]; CHECK_ROWS];
+      crssub::csub(&check_in,width,CHECK_ROWS,&mut expected);
+      for tail in [crssub_simd::Tail::Scalar,crssub_simd::Tail::Masked] {
+         let mut out = vec![vec![UNTOUCHED; MAX_CHECK_WIDTH + 8]; CHECK_ROWS];
+         crssub_simd::csub(&check_in,width,CHECK_ROWS,&mut out,tail);
+         if out != expected {
+            println! ("Error: simd kernel with the {:?} tail wrong for rows of {} elements",
+                                                                               tail,width);
+            errors += 1;
+         }
+      }
+   }
+   if errors == 0 {
+      println! ("Scalar and masked tails agree with csub for rows of 1 to {} elements",
+                                                                       MAX_CHECK_WIDTH);
+   }
+
+   let times = compare_kernels(options,in_array,&[
+      ("masked tail",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  crssub_simd::csub(input_array,nx,ny,output_array,crssub_simd::Tail::Masked)),
+      ("scalar tail",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  crssub_simd::csub(input_array,nx,ny,output_array,crssub_simd::Tail::Scalar))]);
+   let elements = (nrpt * nx * ny).max(1) as f64;
+   println! ("Masked tail {:.4} ns/element, scalar tail {:.4} ns/element",
+                                          times[0] / elements,times[1] / elements);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C o m p a r e  A s y n c
+//
+//   Implements --async. Times nrpt direct calls to csub(), and then nrpt
+//   calls each passed to the blocking thread of a crsasync::BlockingPool and
+//   waited for using crsasync::block_on(), as async code would await them.
+//   A job run on another thread has to own what it uses, so the input array
+//   is shared using an Arc, and the output array is moved in with each job
+//   and handed back as its result. Both sets of calls use the same arrays,
+//   and the output of the last call is checked.
+
+#[cfg(feature = "async")]
+fn compare_async (options: &Options) {
+   use std::sync::Arc;
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let mut in_array = vec![vec![0.0f32; nx]; ny];
+   for (iy,row) in in_array.iter_mut().enumerate() {
+      for (ix,value) in row.iter_mut().enumerate() {
+         *value = (nx - ix + ny - iy) as f32;
+      }
+   }
+   let in_array = Arc::new(in_array);
+   let mut out_array = vec![vec![0.0f32; nx]; ny];
+   crssub::csub(&in_array,nx,ny,&mut out_array);
+
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub::csub(&in_array,nx,ny,&mut out_array);
+   }
+   let direct_nanosecs = start.elapsed().as_nanos() as f64;
+
+   let pool = crsasync::BlockingPool::new();
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      let input = in_array.clone();
+      let mut output = out_array;
+      out_array = crsasync::block_on(pool.spawn_blocking(move || {
+         crssub::csub(&input,nx,ny,&mut output);
+         output
+      }));
+   }
+   let handoff_nanosecs = start.elapsed().as_nanos() as f64;
+   drop(pool);
+
+   check_output(options,"csub",&in_array,&out_array);
+   println! ("Direct time {:.6} sec, handed to another thread {:.6} sec, ratio {:.3}",
+             direct_nanosecs * 1.0e-9,handoff_nanosecs * 1.0e-9,
+                                            handoff_nanosecs / direct_nanosecs);
+   if nrpt > 0 {
+      println! ("Extra time per call {:.3} microsec, for the hand-off in crsasync.rs",
+                        (handoff_nanosecs - direct_nanosecs) * 1.0e-3 / nrpt as f64);
+      println! ("(a minimal thread and block_on(), not a tokio or other async runtime)");
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          S p a r s e  C o o r d s
+//
+//   Returns the list of (ix,iy) coordinates of the elements updated by the
+//   sparse version of csub(). There are --updates of them, or one for every
+//   hundred elements by default, chosen at random using the --seed value, or
+//   a fixed seed if none was given, so the same list is used for every run.
+//   The list is checked, although being generated it should always be good.
+
+fn sparse_coords (options: &Options) -> Vec<(usize,usize)> {
+   let (nx,ny) = (options.nx,options.ny);
+   if nx == 0 || ny == 0 {
+      return Vec::new();
+   }
+   let count = if options.updates > 0 { options.updates } else { (nx * ny / 100).max(1) };
+   let mut random = crsrandom::Random::new(options.seed.unwrap_or(1));
+   let coords: Vec<(usize,usize)> = (0..count).map(|_| {
+      let ix = (random.next_u64() % nx as u64) as usize;
+      let iy = (random.next_u64() % ny as u64) as usize;
+      (ix,iy)
+   }).collect();
+   if let Err(error) = crssub_sparse::check_coords(&coords,nx,ny) {
+      println!("Error: {}",error);
+      process::exit(1);
+   }
+   coords
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C h e c k  S p a r s e
+//
+//   The equivalent of check_output() for the sparse version of csub(). The
+//   elements in the list of coordinates should have the usual values, and
+//   all the others should still have the zero they started with. Reports the
+//   first element that is wrong, if any, and returns true if all is well.
+
+fn check_sparse (options: &Options,in_array: &[Vec<f32>],out_array: &[Vec<f32>],
+                                               coords: &[(usize,usize)]) -> bool {
+   let (nx,ny) = (options.nx,options.ny);
+   let mut written = vec![vec![false; nx]; ny];
+   for &(ix,iy) in coords {
+      written[iy][ix] = true;
+   }
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = if written[iy][ix] { in_array[iy][ix] + (ix + iy) as f32 }
+                                                                    else { 0.0 };
+         if out_array[iy][ix] != expected {
+            println! ("Error {} {} {} {}",ix,iy,out_array[iy][ix],in_array[iy][ix]);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             S u m  A r r a y
+//
+//   Returns the sum of all the elements of an array, as an f64, adding them
+//   row by row in the same order as the checksum versions of csub() do.
+
+fn sum_array (array: &[Vec<f32>]) -> f64 {
+   let mut sum = 0.0f64;
+   for row in array {
+      for value in row {
+         sum += *value as f64;
+      }
+   }
+   sum
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            C o m p a r e  F o l d
+//
+//   Times the same number of calls to the fold version of csub(), to the
+//   checksum version in crssub_checksum.rs, which also works out the
+//   checksum as it goes, and to the normal version in crssub.rs followed by
+//   sum_array(), which gets the same checksum in a second pass, using
+//   compare_kernels(). Then checks the three checksums agree.
+
+fn compare_fold (options: &Options,in_array: &Vec<Vec<f32>>) {
+   let sums = [Cell::new(0.0f64),Cell::new(0.0f64),Cell::new(0.0f64)];
+   compare_kernels(options,in_array,&[
+      ("fold",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  sums[0].set(crssub_fold::csub(input_array,nx,ny,output_array))),
+      ("checksum",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  sums[1].set(crssub_checksum::csub(input_array,nx,ny,output_array))),
+      ("csub and sum",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>| {
+                  crssub::csub(input_array,nx,ny,output_array);
+                  sums[2].set(sum_array(output_array));
+               })]);
+   let checksums = sums.map(|sum| sum.get());
+   if checksums[0] != checksums[1] || checksums[0] != checksums[2] {
+      println! ("Error: checksums differ, fold {}, checksum {}, separate sum {}",
+                                             checksums[0],checksums[1],checksums[2]);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C h e c k  P r e f i x
+//
+//   The equivalent of check_output() for the prefix version of csub(). Works
+//   out the running sum along each row independently, adding the terms in
+//   the same order, so the results should match exactly. Reports the first
+//   element that is wrong, if any, and returns true if all is well.
+
+fn check_prefix (options: &Options,in_array: &[Vec<f32>],out_array: &[Vec<f32>]) -> bool {
+   for iy in 0..options.ny {
+      let mut running = 0.0f32;
+      for ix in 0..options.nx {
+         running += in_array[iy][ix] + (ix + iy) as f32;
+         if out_array[iy][ix] != running {
+            println! ("Error {} {} {} {} {}",ix,iy,out_array[iy][ix],in_array[iy][ix],
+                                                                          running);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C o m p a r e  S p a r s e
+//
+//   Times the same number of calls to the sparse version of csub(), with the
+//   list of coordinates passed, and to the dense version in crssub.rs, using
+//   compare_kernels(), and then prints the time per element written by each,
+//   and the ratio.
+
+fn compare_sparse (options: &Options,in_array: &Vec<Vec<f32>>,coords: &[(usize,usize)]) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let times = compare_kernels(options,in_array,&[
+      ("sparse",&|input_array: &Vec<Vec<f32>>,nx,ny,output_array: &mut Vec<Vec<f32>>|
+                  crssub_sparse::csub(input_array,nx,ny,output_array,coords)),
+      ("csub",&crssub::csub)]);
+   let sparse_per_element = times[0] / (nrpt * coords.len()).max(1) as f64;
+   let dense_per_element = times[1] / (nrpt * nx * ny).max(1) as f64;
+   println! ("Sparse {:.4} ns per element, dense {:.4} ns per element, ratio {:.3}",
+             sparse_per_element,dense_per_element,sparse_per_element / dense_per_element);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C o m p a r e  V e c t o r
+//
+//   Times the vector version of csub() against the general version in
+//   crssub.rs for an array with a single row and one with a single column,
+//   each with the same number of elements as the arrays for the main test,
+//   and nrpt calls in each case. Checks that both versions give the same
+//   results, and prints the times and their ratio for each shape.
+
+fn compare_vector (options: &Options) {
+   let nrpt = options.nrpt;
+   let elements = options.nx * options.ny;
+   for (nx,ny) in [(elements,1),(1,elements)] {
+      let mut in_array = vec![vec![0.0f32; nx]; ny];
+      for (iy,row) in in_array.iter_mut().enumerate() {
+         for (ix,value) in row.iter_mut().enumerate() {
+            *value = (nx - ix + ny - iy) as f32;
+         }
+      }
+      let mut vector_array = vec![vec![-1.0f32; nx]; ny];
+      let mut csub_array = vec![vec![-2.0f32; nx]; ny];
+      let start = Instant::now();
+      for _irpt in 1..=nrpt {
+         crssub_vector::csub(&in_array,nx,ny,&mut vector_array);
+      }
+      let vector_nanosecs = start.elapsed().as_nanos() as f64;
+      let start = Instant::now();
+      for _irpt in 1..=nrpt {
+         crssub::csub(&in_array,nx,ny,&mut csub_array);
+      }
+      let csub_nanosecs = start.elapsed().as_nanos() as f64;
+      println! ("{} by {}: vector time {:.6} sec, csub time {:.6} sec, ratio {:.3}",
+                ny,nx,vector_nanosecs * 1.0e-9,csub_nanosecs * 1.0e-9,
+                                               vector_nanosecs / csub_nanosecs);
+      if nrpt > 0 && vector_array != csub_array {
+         println! ("Error: vector and csub versions differ for {} by {}",ny,nx);
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       C o m p a r e  R e c u r s i v e
+//
+//   First checks that the recursive version of csub() and the tiled version
+//   that goes with it give exactly the same results as the naive version in
+//   crssub.rs, for the input array and for a range of small sizes either
+//   side of where the recursion stops and the tiles end. Then times the
+//   same number of calls to the recursive version, to the tiled version and
+//   to the iterator version in crssub_iter.rs, which has the same inner loop
+//   but simply works through the rows in order, using compare_kernels().
+
+fn compare_recursive (options: &Options,in_array: &Vec<Vec<f32>>) {
+   let (nx,ny) = (options.nx,options.ny);
+   let check = |in_array: &Vec<Vec<f32>>,nx: usize,ny: usize| {
+      let mut expected = in_array.clone();
+      crssub::csub(in_array,nx,ny,&mut expected);
+      let mut recursive_array = in_array.clone();
+      crssub_recursive::csub(in_array,nx,ny,&mut recursive_array);
+      let mut tiled_array = in_array.clone();
+      crssub_recursive::csub_tiled(in_array,nx,ny,&mut tiled_array);
+      if recursive_array != expected {
+         println! ("Error: recursive version differs from csub for {} by {}",ny,nx);
+      }
+      if tiled_array != expected {
+         println! ("Error: tiled version differs from csub for {} by {}",ny,nx);
+      }
+   };
+   check(in_array,nx,ny);
+   for check_ny in [1,7,8,9,16,17,33] {
+      for check_nx in [1,63,64,65,130] {
+         let small_array: Vec<Vec<f32>> = (0..check_ny).map(|iy|
+               (0..check_nx).map(|ix| (check_nx - ix + check_ny - iy) as f32).collect()).collect();
+         check(&small_array,check_nx,check_ny);
+      }
+   }
+   compare_kernels(options,in_array,&[("recursive",&crssub_recursive::csub),
+            ("tiled",&crssub_recursive::csub_tiled),("iter",&crssub_iter::csub)]);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          W r i t e  R e c o r d
+//
+//   Writes the result of the test to the files specified by --csv and
+//   --json, if any. With --auto-name, a format given without a file name
+//   gets an automatically generated name, and if no format was given at
+//   all, CSV is used.
+
+fn write_record (options: &Options,nanosecs: f64) {
+   let record = crsresult::Record {
+      kernel: options.kernel_name.clone(),
+      nx: options.nx,
+      ny: options.ny,
+      nrpt: options.nrpt,
+      narrays: options.narrays,
+      nanosecs,
+   };
+   write_records(options,&[record]);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         W r i t e  R e c o r d s
+//
+//   Writes a set of results to the files specified by --csv and --json, if
+//   any, as for write_record(), all to the same files.
+
+fn write_records (options: &Options,records: &[crsresult::Record]) {
+   let mut csv_file = options.csv_file.clone();
+   let mut json_file = options.json_file.clone();
+   if options.auto_name {
+      let csv_wanted = env::args().any(|arg| arg.starts_with("--csv"));
+      let json_wanted = env::args().any(|arg| arg.starts_with("--json"));
+      if csv_file.is_empty() && (csv_wanted || !json_wanted) {
+         csv_file = crsresult::auto_name("crsmain","csv");
+      }
+      if json_file.is_empty() && json_wanted {
+         json_file = crsresult::auto_name("crsmain","json");
+      }
+   }
+   let results = if records.len() == 1 { "Result" } else { "Results" };
+   let settings = if options.machine_readable { settings(options) } else { Vec::new() };
+   if !csv_file.is_empty() {
+      match records.iter()
+               .try_for_each(|record| crsresult::append_csv(&csv_file,record,&settings)) {
+         Ok(()) => println!("{} written to {}",results,csv_file),
+         Err(error) => println!("Unable to write {}: {}",csv_file,error),
+      }
+   }
+   if !json_file.is_empty() {
+      match records.iter()
+               .try_for_each(|record| crsresult::append_json(&json_file,record,&settings)) {
+         Ok(()) => println!("{} written to {}",results,json_file),
+         Err(error) => println!("Unable to write {}: {}",json_file,error),
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             S e t t i n g s
+//
+//   Returns the settings written with each result by --machine-readable, as
+//   name and value pairs. These are the values actually used, after any
+//   defaults have been applied, not just what was on the command line. The
+//   kernel, dimensions, repeat count and number of arrays are already in
+//   every record, so they aren't repeated here.
+
+fn settings (options: &Options) -> Vec<(&'static str,String)> {
+   let fill = if !options.input_file.is_empty() {
+      format!("file:{}",options.input_file)
+   } else if options.seed.is_some() {
+      String::from(if options.seed_per_repeat { "random-per-repeat" } else { "random" })
+   } else if options.kernel_name == "branchy" {
+      String::from("scrambled")
+   } else {
+      String::from("descending")
+   };
+   let seed = options.seed.map_or(String::from("none"),|seed| seed.to_string());
+   let cpus = match crsos::get_affinity() {
+      Some(mask) => cpu_list(&mask.cpus()),
+      None => String::from("unknown"),
+   };
+   let endian = match options.endian {
+      Endian::Little => "little",
+      Endian::Big => "big",
+   };
+   vec![
+      ("dtype",String::from("f32")),
+      ("warmups",options.max_warmups.to_string()),
+      ("burn_in_ms",options.burn_in.to_string()),
+      ("fill",fill),
+      ("seed",seed),
+      ("repeat_pattern",options.repeat_pattern.to_string()),
+      ("zero_output",options.zero_output.to_string()),
+      ("threads",options.nthreads.to_string()),
+      ("numa_first_touch",options.numa_first_touch.to_string()),
+      ("cpus",cpus),
+      ("endian",endian.to_string()),
+      ("op",format!("{:?}",options.op).to_lowercase()),
+      ("tail",format!("{:?}",options.tail).to_lowercase()),
+      ("rustc",crsresult::rustc_version()),
+      ("opt",crsresult::opt_level()),
+   ]
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             C P U  L i s t
+//
+//   Returns a list of processor numbers in the compact form used by Linux,
+//   with runs of consecutive numbers given as ranges, eg "0-3,8,10-11".
+
+fn cpu_list (cpus: &[usize]) -> String {
+   let mut ranges: Vec<String> = Vec::new();
+   let mut index = 0;
+   while index < cpus.len() {
+      let first = cpus[index];
+      let mut last = first;
+      while index + 1 < cpus.len() && cpus[index + 1] == last + 1 {
+         index += 1;
+         last = cpus[index];
+      }
+      ranges.push(if first == last { first.to_string() }
+                                    else { format!("{}-{}",first,last) });
+      index += 1;
+   }
+   ranges.join(",")
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C h e c k  O u t p u t
+//
+//   Checks every element of an output array against the value expected from
+//   the corresponding element of the input array, and reports the first one
+//   that is wrong, if any. The expected value is worked out from the input
+//   array itself, not from the formula used to fill it, so this works however
+//   the input was set up - generated, scrambled, or read from a file - and it
+//   doesn't matter in what order the elements were processed, which is what
+//   the threads version of csub() needs. The name is that of the version of
+//   csub() used, as in KERNELS. The sparse version only writes some of the
+//   output, and in the prefix version each element depends on the one before
+//   it, so they have their own checks. Returns true if all is well.
+
+fn check_output (options: &Options,name: &str,in_array: &[Vec<f32>],
+                                                  out_array: &[Vec<f32>]) -> bool {
+   match name {
+      "sparse" => return check_sparse(options,in_array,out_array,&sparse_coords(options)),
+      "prefix" => return check_prefix(options,in_array,out_array),
+      _ => {}
+   }
+   for iy in 0..options.ny {
+      for ix in 0..options.nx {
+         if out_array[iy][ix] != expected_value(options,name,in_array,ix,iy) {
+            println! ("Error {} {} {} {}",ix,iy,out_array[iy][ix],in_array[iy][ix]);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        E x p e c t e d  V a l u e
+//
+//   Returns the value expected in the output array for element [iy][ix],
+//   given the input array and the name of the version of csub() used. This is
+//   the input value plus the index sum, except for those versions of csub()
+//   that deliberately do something different.
+
+fn expected_value (options: &Options,name: &str,in_array: &[Vec<f32>],
+                                               ix: usize,iy: usize) -> f32 {
+   match name {
+      "memcpy" => in_array[iy][ix],
+      "div" => in_array[iy][ix] / ((ix + iy) as f32 + 1.0),
+      "branchy" if in_array[iy][ix] <= options.threshold => in_array[iy][ix],
+      "op" => {
+         let (value,sum) = (in_array[iy][ix],(ix + iy) as f32);
+         match options.op {
+            crssub_op::Op::Add => value + sum,
+            crssub_op::Op::Mul => value * sum,
+            crssub_op::Op::Div => value / (sum + 1.0),
+            crssub_op::Op::Copy => value,
+            crssub_op::Op::SqAdd => value + sum * sum,
+         }
+      }
+      _ => in_array[iy][ix] + (ix + iy) as f32,
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                    C h e c k  R e f e r e n c e  S u m
+//
+//   Implements --check-reference-sum. Adds up all the elements of each of
+//   the output arrays passed and compares each total with the one expected
+//   for the usual input values, printing both. Every output element should
+//   then be in[iy][ix] + ix + iy = (nx - ix + ny - iy) + ix + iy = nx + ny,
+//   so the expected total is simply nx * ny * (nx + ny). Returns false if
+//   any total differs, and true if all is well or the check can't be used.
+
+fn check_reference_sum (options: &Options,out_arrays: &[Vec<Vec<f32>>]) -> bool {
+   let usual_input = options.input_file.is_empty() && options.seed.is_none()
+                          && !options.seed_per_repeat && !options.repeat_pattern;
+   let adds_index_sum = match options.kernel_name.as_str() {
+      "memcpy" | "div" | "branchy" | "sparse" | "prefix" => false,
+      "op" => options.op == crssub_op::Op::Add,
+      _ => true,
+   };
+   if !usual_input || !adds_index_sum {
+      println! ("The reference sum only applies to the usual input values and kernels, ignored");
+      return true;
+   }
+   let (nx,ny) = (options.nx,options.ny);
+   let expected = (nx * ny) as f64 * (nx + ny) as f64;
+   let mut all_ok = true;
+   for out_array in out_arrays.iter() {
+      let actual: f64 = out_array.iter()
+                  .map(|row| row[..nx].iter().map(|&value| value as f64).sum::<f64>()).sum();
+      let ok = (actual - expected).abs() <= 1.0e-9 * expected;
+      println! ("Reference sum {}, actual sum {}{}",expected,actual,
+                                                 if ok { "" } else { " - WRONG" });
+      all_ok = all_ok && ok;
+   }
+   all_ok
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        R e p o r t  R o o f l i n e
+//
+//   Given the time in nanoseconds taken by the normal test, reports it
+//   together with the minimum time it could take given the peak memory
+//   bandwidth specified by --roofline, and the fraction of that peak that
+//   was achieved. Each call reads the input array and writes the output
+//   array, so moves 8 bytes per element. (A byte per nanosecond is a GByte
+//   per second, which keeps the arithmetic simple.)
+
+fn report_roofline (options: &Options,nanosecs: f64) {
+   let bytes = 8.0 * (options.nrpt * options.nx * options.ny) as f64;
+   let minimum = bytes / options.roofline;
+   println!("Measured time {:.6} sec, bandwidth-bound minimum {:.6} sec",
+                                           nanosecs * 1.0e-9,minimum * 1.0e-9);
+   println!("Achieved {:.2} GB/s, {:.1}% of the {} GB/s peak",
+           bytes / nanosecs,100.0 * minimum / nanosecs,options.roofline);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      C a l l  R e p e a t e d l y
+//
+//   Calls the supplied version of csub() nrpt times, cycling through the
+//   array pairs in turn. This is the normal test, which measures throughput
+//   when the program as a whole is timed. With --zero-output, each output
+//   array is cleared just before each call made with it.
+
+fn call_repeatedly<F> (kernel: F,options: &Options,
+             in_arrays: &mut [Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>])
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let narrays = in_arrays.len();
+   let mut iarray = 0;
+   if options.seed_per_repeat && options.nx > 0 && options.ny > 0 {
+
+      //  With --seed-per-repeat, a row of the input array is refilled with
+      //  new random values before each call. As with --repeat-pattern, the
+      //  final check compares each output array with its input array as it
+      //  was for the last call made with it, so still works.
+
+      for irpt in 1..=options.nrpt {
+         refill_row(options,irpt,&mut in_arrays[iarray]);
+         if options.zero_output { zero_array(&mut out_arrays[iarray]); }
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         iarray += 1;
+         if iarray >= narrays { iarray = 0; }
+      }
+   } else if options.repeat_pattern && options.nx > 0 && options.ny > 0 {
+
+      //  With --repeat-pattern, the first element of the input array is
+      //  changed before each call, to its original value plus the repeat
+      //  number. Since the input is different each time, the compiler can't
+      //  conclude that repeated calls produce the same result and so only
+      //  need to be made once. The final check of the results still works,
+      //  since it compares each output array with its input array as it was
+      //  for the last call made with it.
+
+      let originals: Vec<f32> = in_arrays.iter().map(|array| array[0][0]).collect();
+      for irpt in 1..=options.nrpt {
+         in_arrays[iarray][0][0] = originals[iarray] + irpt as f32;
+         if options.zero_output { zero_array(&mut out_arrays[iarray]); }
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         iarray += 1;
+         if iarray >= narrays { iarray = 0; }
+      }
+   } else {
+      for _irpt in 1..=options.nrpt {
+         if options.zero_output { zero_array(&mut out_arrays[iarray]); }
+         kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+         iarray += 1;
+         if iarray >= narrays { iarray = 0; }
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         G e n e r a t e  I m a g e
+//
+//   Implements --generate. Writes an image of ny rows of nx columns to the
+//   file named, using crsfile::write_f32_file() in the byte order given by
+//   --endian, with each element set to its number counting along the rows.
+//   Exits with a status of 1 if the file can't be written.
+
+fn generate_image (options: &Options) {
+   let (nx,ny) = (options.nx,options.ny);
+   let image: Vec<Vec<f32>> =
+          (0..ny).map(|iy| (0..nx).map(|ix| (iy * nx + ix) as f32).collect()).collect();
+   if let Err(error) =
+             crsfile::write_f32_file(&options.generate_file,&image,options.endian) {
+      println!("Unable to write {}: {}",options.generate_file,error);
+      process::exit(1);
+   }
+   let order = match options.endian {
+      Endian::Little => "little",
+      Endian::Big => "big",
+   };
+   println!("Wrote {} rows of {} columns to {}, {}-endian",
+                                          ny,nx,options.generate_file,order);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                     C o m p a r e  C S V  F i l e s
+//
+//   Implements --compare-csv. The value is the names of the old and new
+//   results files, separated by a comma. Each result is identified by the
+//   version of csub() and the array dimensions; the other columns, such as
+//   the number of calls, don't have to match. The results are listed in
+//   the order they first appear in the old file, followed by any that are
+//   only in the new one. Exits with a status of 1 if the value isn't two
+//   file names or if either file can't be read.
+
+fn compare_csv_files (value: &str) {
+   let Some((old_path,new_path)) = value.split_once(',') else {
+      println!("--compare-csv needs two file names separated by a comma, not {}",value);
+      process::exit(1);
+   };
+   let read = |path: &str| crsresult::read_csv(path).unwrap_or_else(|error| {
+      println!("Unable to read {}: {}",path,error);
+      process::exit(1);
+   });
+   let (old_records,new_records) = (read(old_path),read(new_path));
+
+   //  Keep the last result for each version and size from each file, and
+   //  the order each was first seen in.
+
+   type Key = (String,usize,usize);
+   let mut keys: Vec<Key> = Vec::new();
+   let mut old_times: HashMap<Key,f64> = HashMap::new();
+   let mut new_times: HashMap<Key,f64> = HashMap::new();
+   for (records,times) in [(&old_records,&mut old_times),(&new_records,&mut new_times)] {
+      for record in records.iter() {
+         let key = (record.kernel.clone(),record.nx,record.ny);
+         if !keys.contains(&key) {
+            keys.push(key.clone());
+         }
+         times.insert(key,record.ns_per_element());
+      }
+   }
+   let colour = io::stdout().is_terminal();
+   let mut regressions = 0;
+   println!("{:>12} {:>8} {:>8} {:>12} {:>12} {:>9}","Kernel","Columns","Rows",
+                                                 "Old ns/el","New ns/el","Change");
+   for key in keys.iter() {
+      let (kernel,nx,ny) = key;
+      match (old_times.get(key),new_times.get(key)) {
+         (Some(old),Some(new)) => {
+            let change = if *old > 0.0 { (new - old) / old } else { 0.0 };
+            let mut line = format!("{:>12} {:>8} {:>8} {:>12.4} {:>12.4} {:>8.1}%",
+                                          kernel,nx,ny,old,new,100.0 * change);
+            if change > REGRESSION_THRESHOLD {
+               regressions += 1;
+               line = if colour {
+                  format!("\x1b[31m{}  REGRESSION\x1b[0m",line)
+               } else {
+                  format!("{}  REGRESSION",line)
+               };
+            }
+            println!("{}",line);
+         }
+         (Some(old),None) => println!("{:>12} {:>8} {:>8} {:>12.4} {:>12} {:>9}",
+                                                 kernel,nx,ny,old,"-","only old"),
+         (None,Some(new)) => println!("{:>12} {:>8} {:>8} {:>12} {:>12.4} {:>9}",
+                                                 kernel,nx,ny,"-",new,"only new"),
+         (None,None) => {}
+      }
+   }
+   println!("{} regression{} of more than {:.0}%",regressions,
+                    if regressions == 1 { "" } else { "s" },100.0 * REGRESSION_THRESHOLD);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         R e p o r t  B a s e l i n e
+//
+//   Implements --baseline. Prints the time taken by the test, and the time
+//   per element, and, if the baseline file has a result for the same version
+//   of csub() and array size, the percentage change from the last such
+//   result, as --compare-csv would work it out. A missing or unreadable file
+//   isn't an error; there is just nothing to compare with.
+
+fn report_baseline (options: &Options,nanosecs: f64) {
+   let path = &options.baseline_file;
+   let record = crsresult::Record {
+      kernel: options.kernel_name.clone(),
+      nx: options.nx,
+      ny: options.ny,
+      nrpt: options.nrpt,
+      narrays: options.narrays,
+      nanosecs,
+   };
+   let ns_per_element = record.ns_per_element();
+   let result = format!("Time {:.6} sec, {:.4} ns per element",nanosecs * 1.0e-9,
+                                                                   ns_per_element);
+   let baseline = match crsresult::read_csv(path) {
+      Ok(records) => records.into_iter().rev().find(|baseline|
+            baseline.kernel == record.kernel && baseline.nx == record.nx
+                                             && baseline.ny == record.ny),
+      Err(error) => {
+         println!("Unable to read baseline {}: {}",path,error);
+         None
+      }
+   };
+   let Some(baseline) = baseline else {
+      println!("{} (no baseline for {} with {} rows of {} columns)",result,
+                                               record.kernel,record.ny,record.nx);
+      return;
+   };
+   let old = baseline.ns_per_element();
+   let change = if old > 0.0 { (ns_per_element - old) / old } else { 0.0 };
+
+   //  A change that rounds to 0.0% is reported as no change, rather than as
+   //  0.0% faster or slower.
+
+   let percent = format!("{:.1}",100.0 * change.abs());
+   let mut line = if percent == "0.0" {
+      format!("{}, same as the baseline {:.4} ns per element",result,old)
+   } else {
+      let comparison = if change > 0.0 { "slower" } else { "faster" };
+      format!("{}, {}% {} than the baseline {:.4} ns per element",
+                                                   result,percent,comparison,old)
+   };
+   if change > REGRESSION_THRESHOLD {
+      line = if io::stdout().is_terminal() {
+         format!("\x1b[31m{}  REGRESSION\x1b[0m",line)
+      } else {
+         format!("{}  REGRESSION",line)
+      };
+   }
+   println!("{}",line);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            Z e r o  A r r a y
+//
+//   Sets every element of an array to zero, for --zero-output. Each row is
+//   cleared using fill(), which the compiler turns into a memset() call.
+
+fn zero_array (array: &mut [Vec<f32>]) {
+   for row in array.iter_mut() {
+      row.fill(0.0);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                             R e f i l l  R o w
+//
+//   Refills one row of an input array with new random values for repeat
+//   number irpt, for --seed-per-repeat. The generator is reseeded from the
+//   seed and the repeat number, so the values for a given repeat are always
+//   the same, and successive repeats refill successive rows. Only nx values
+//   are generated, compared to the nx * ny elements each call processes, so
+//   this is cheap unless there are very few rows.
+
+fn refill_row (options: &Options,irpt: usize,in_array: &mut [Vec<f32>]) {
+   let seed = options.seed.unwrap_or(1).wrapping_add(irpt as u64);
+   let mut random = crsrandom::Random::new(seed);
+   let iy = irpt % options.ny;
+   for value in in_array[iy][..options.nx].iter_mut() {
+      *value = random.next_f32();
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                              R u n  D u a l
+//
+//   Runs the normal test for the dual kernel, which writes to two output
+//   arrays. The first set of output arrays is checked by the main routine
+//   in the usual way; this checks the second set, which should have the
+//   index sum subtracted rather than added.
+
+fn run_dual (options: &Options,in_arrays: &[Vec<Vec<f32>>],
+     out_arrays: &mut [Vec<Vec<f32>>],second_arrays: &mut [Vec<Vec<f32>>]) -> f64 {
+   let (nx,ny) = (options.nx,options.ny);
+   let narrays = in_arrays.len();
+   let mut iarray = 0;
+   let start = Instant::now();
+   for _irpt in 1..=options.nrpt {
+      crssub_dual::csub_dual (&in_arrays[iarray],nx,ny,&mut out_arrays[iarray],
+                                                   &mut second_arrays[iarray]);
+      iarray += 1;
+      if iarray >= narrays { iarray = 0; }
+   }
+   let nanosecs = start.elapsed().as_nanos() as f64;
+   'check_loop :
+   for (in_array,second_array) in in_arrays.iter().zip(second_arrays.iter())
+                                            .take(options.nrpt.min(narrays)) {
+      for iy in 0..ny {
+         for ix in 0..nx {
+            if second_array[iy][ix] != (in_array[iy][ix] - (ix + iy) as f32) {
+               println! ("Error in second output {} {} {} {}",
+                              ix,iy,second_array[iy][ix],in_array[iy][ix]);
+               break 'check_loop;
+            }
+         }
+      }
+   }
+   nanosecs
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C o u n t  C y c l e s
+//
+//   Calls the supplied version of csub() nrpt times, just as for the normal
+//   test, but reads the processor's time stamp counter before and after all
+//   the calls and reports the number of cycles per element. The elapsed
+//   time is measured as well, which gives the rate at which the counter
+//   runs - which on a modern processor is its nominal clock frequency.
+
+fn count_cycles<F> (kernel: F,options: &Options,
+             in_arrays: &mut [Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>]) -> f64
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   if read_time_stamp().is_none() {
+      println!("--cycles is only supported on x86_64, ignored");
+      let start = Instant::now();
+      call_repeatedly(kernel,options,in_arrays,out_arrays);
+      return start.elapsed().as_nanos() as f64;
+   }
+   let start = Instant::now();
+   let start_cycles = read_time_stamp().unwrap();
+   call_repeatedly(kernel,options,in_arrays,out_arrays);
+   let end_cycles = read_time_stamp().unwrap();
+   let nanosecs = start.elapsed().as_nanos() as f64;
+   let cycles = end_cycles.wrapping_sub(start_cycles) as f64;
+   let elements = (options.nrpt * options.nx * options.ny).max(1) as f64;
+   println!("{} cycles, {:.4} cycles per element, {:.4} ns per element",
+                                  cycles,cycles / elements,nanosecs / elements);
+   println!("Time stamp counter rate {:.3} GHz",cycles / nanosecs);
+   println!("(These are reference cycles, counted at a constant rate, not core cycles)");
+   nanosecs
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       R e a d  T i m e  S t a m p
+//
+//   Returns the current value of the processor's time stamp counter, or None
+//   if this isn't available. On x86_64, the rdtsc instruction isn't itself
+//   serializing - it can be executed before earlier instructions finish, or
+//   after later ones start - so it is bracketed by lfence instructions,
+//   which wait for all earlier instructions to complete.
+
+#[cfg(target_arch = "x86_64")]
+fn read_time_stamp () -> Option<u64> {
+   use std::arch::x86_64::{_mm_lfence,_rdtsc};
+   unsafe {
+      _mm_lfence();
+      let cycles = _rdtsc();
+      _mm_lfence();
+      Some(cycles)
+   }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn read_time_stamp () -> Option<u64> {
+   None
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         T i m e  E a c h  C a l l
+//
+//   Calls the supplied version of csub() nrpt times, cycling through the
+//   array pairs in turn, but times each call individually and returns the
+//   times, in nanoseconds, in the order the calls were made. If --latency
+//   was specified, the arrays are first flushed from the cache and a memory
+//   fence is used to make sure nothing from the previous call is still in
+//   progress. This then measures the latency of a single cold call, rather
+//   than the throughput of many back-to-back calls.
+
+fn time_each_call<F> (kernel: F,options: &Options,
+      in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>]) -> Vec<f64>
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let narrays = in_arrays.len();
+   let mut iarray = 0;
+   let mut times: Vec<f64> = Vec::with_capacity(options.nrpt);
+   for _irpt in 1..=options.nrpt {
+      if options.latency {
+         flush_from_cache(&in_arrays[iarray]);
+         flush_from_cache(&out_arrays[iarray]);
+         atomic::fence(atomic::Ordering::SeqCst);
+      }
+      let start = Instant::now();
+      if options.zero_output {
+         zero_array(&mut out_arrays[iarray]);
+      }
+      kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+      if options.latency {
+         atomic::fence(atomic::Ordering::SeqCst);
+      }
+      times.push(start.elapsed().as_nanos() as f64);
+      iarray += 1;
+      if iarray >= narrays { iarray = 0; }
+   }
+   times
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                   A c c u m u l a t e  E a c h  C a l l
+//
+//   Does the same as time_each_call(), but adds each time to an Accumulator
+//   as it goes instead of keeping it, so the memory used doesn't depend on
+//   the number of calls, and returns the Accumulator.
+
+fn accumulate_each_call<F> (kernel: F,options: &Options,
+      in_arrays: &[Vec<Vec<f32>>],out_arrays: &mut [Vec<Vec<f32>>]) -> crstime::Accumulator
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let narrays = in_arrays.len();
+   let mut iarray = 0;
+   let mut accumulator = crstime::Accumulator::default();
+   for _irpt in 1..=options.nrpt {
+      if options.latency {
+         flush_from_cache(&in_arrays[iarray]);
+         flush_from_cache(&out_arrays[iarray]);
+         atomic::fence(atomic::Ordering::SeqCst);
+      }
+      let start = Instant::now();
+      if options.zero_output {
+         zero_array(&mut out_arrays[iarray]);
+      }
+      kernel (&in_arrays[iarray],options.nx,options.ny,&mut out_arrays[iarray]);
+      if options.latency {
+         atomic::fence(atomic::Ordering::SeqCst);
+      }
+      accumulator.add(start.elapsed().as_nanos() as f64);
+      iarray += 1;
+      if iarray >= narrays { iarray = 0; }
+   }
+   accumulator
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                  P r i n t  R u n n i n g  S u m m a r y
+//
+//   Prints the same as print_summary(), in microseconds, for a set of times
+//   that weren't kept, apart from the median and the percentiles, which
+//   can't be worked out from the running statistics.
+
+fn print_running_summary (title: &str,accumulator: &crstime::Accumulator) {
+   let summary = accumulator.summary();
+   println!("{}, {} calls, in microseconds:",title,summary.count);
+   println!("   min {:.3}  mean {:.3}  max {:.3}  stddev {:.3}",
+          summary.min / 1000.0,summary.mean / 1000.0,summary.max / 1000.0,
+                                                      summary.stddev / 1000.0);
+   println!("   mean {:.3} \u{b1} {:.3} (95% confidence)  standard error {:.3}",
+              summary.mean / 1000.0,summary.confidence_95() / 1000.0,
+                                             summary.standard_error() / 1000.0);
+   let cv = summary.coefficient_of_variation();
+   println!("   coefficient of variation {:.2}%",100.0 * cv);
+   if cv > CV_THRESHOLD {
+      println!("   UNSTABLE MEASUREMENT: variation above {:.0}%. Try more calls,",
+                                                          100.0 * CV_THRESHOLD);
+      println!("   --warmup, or pinning to one processor, eg using taskset.");
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          P r i n t  S u m m a r y
+//
+//   Prints the distribution of a set of per-call times, in microseconds.
+//   This sorts the times, so they are no longer in the order the calls were
+//   made once it returns.
+
+fn print_summary (title: &str,times: &mut [f64]) {
+   let summary = crstime::summarise(times);
+   println!("{}, {} calls, in microseconds:",title,summary.count);
+   println!("   min {:.3}  median {:.3}  mean {:.3}  max {:.3}  stddev {:.3}",
+          summary.min / 1000.0,summary.median / 1000.0,summary.mean / 1000.0,
+                             summary.max / 1000.0,summary.stddev / 1000.0);
+   println!("   90th percentile {:.3}  99th percentile {:.3}",
+              crstime::percentile(times,90.0) / 1000.0,
+                                 crstime::percentile(times,99.0) / 1000.0);
+   println!("   mean {:.3} \u{b1} {:.3} (95% confidence)  standard error {:.3}",
+              summary.mean / 1000.0,summary.confidence_95() / 1000.0,
+                                             summary.standard_error() / 1000.0);
+   let cv = summary.coefficient_of_variation();
+   println!("   coefficient of variation {:.2}%",100.0 * cv);
+   if cv > CV_THRESHOLD {
+      println!("   UNSTABLE MEASUREMENT: variation above {:.0}%. Try more calls,",
+                                                          100.0 * CV_THRESHOLD);
+      println!("   --warmup, or pinning to one processor, eg using taskset.");
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         B e n c h  C o m m a n d
+//
+//   Returns the command line, less any --bench-json argument, for the
+//   "command" field written by --bench-json. Leaving out the file name means
+//   the same test written to different files is still recognised as the
+//   same command.
+
+fn bench_command () -> String {
+   let args: Vec<String> = env::args().filter(|arg| !arg.starts_with("--bench-json")).collect();
+   args.join(" ")
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                     F i r s t  T o u c h  A r r a y
+//
+//   Allocates an array of ny rows of nx columns, with the rows divided into
+//   blocks in the same way as the threads kernel divides them, and with each
+//   block allocated and written to by a separate thread. Linux places each
+//   page of memory on the NUMA node of the processor that first writes to
+//   it, so this should put each block of rows in memory local to the thread
+//   that processes it - assuming the threads are scheduled the same way each
+//   time, which is likely but not certain. This is used for both the input
+//   and output arrays, and because the values in the input array depend only
+//   on the row and column numbers, the input values can be set here as
+//   well, which means the main routine will set them again as usual. That
+//   does no harm, since by then each page has already been placed.
+
+fn first_touch_array (options: &Options) -> Vec<Vec<f32>> {
+   let (nx,ny) = (options.nx,options.ny);
+   let block = crssub_threads::rows_per_thread(ny,options.nthreads);
+   let mut array: Vec<Vec<f32>> = Vec::with_capacity(ny);
+   std::thread::scope(|scope| {
+      let mut handles = Vec::new();
+      let mut first_row = 0;
+      while first_row < ny {
+         let last_row = (first_row + block).min(ny);
+         handles.push(scope.spawn(move || {
+            let mut rows = Vec::with_capacity(last_row - first_row);
+            for iy in first_row..last_row {
+               let mut row = Vec::with_capacity(nx);
+               for ix in 0..nx {
+                  row.push((nx - ix + ny - iy) as f32);
+               }
+               rows.push(row);
+            }
+            rows
+         }));
+         first_row = last_row;
+      }
+      for handle in handles {
+         array.extend(handle.join().unwrap());
+      }
+   });
+   array
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        S w e e p  S i z e s
+//
+//   Returns the array sizes to be tested, as a set of (nx,ny,nrpt) values.
+//   Normally this is just the size specified, but with --sweep it is a set
+//   of increasing widths, with the repeat count scaled so that each size
+//   processes the same total number of elements.
+
+fn sweep_sizes (options: &Options) -> Vec<(usize,usize,usize)> {
+   if !options.sweep {
+      return vec![(options.nx,options.ny,options.nrpt)];
+   }
+   let total = options.nrpt * options.nx * options.ny;
+   let mut sizes = Vec::new();
+   let mut nx = 16;
+   while nx < options.nx {
+      sizes.push(nx);
+      nx *= 2;
+   }
+   sizes.push(options.nx);
+   sizes.iter().map(|&nx| {
+      let nrpt = (total / (nx * options.ny).max(1)).max(1);
+      (nx,options.ny,nrpt)
+   }).collect()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                     C o m p a r e  P r e c i s i o n s
+//
+//   Times the single and double precision versions of the generic csub()
+//   for each of the sizes to be tested, and prints the results side by side.
+//   The bandwidth figure counts both the bytes read and the bytes written,
+//   and since a byte per nanosecond is a GByte per second, is just the
+//   number of bytes moved divided by the time in nanoseconds.
+
+fn compare_precisions (options: &Options) {
+   println!("{:>8} {:>8} {:>12} {:>10} {:>12} {:>10}",
+             "Columns","Rows","f32 ns/elem","f32 GB/s","f64 ns/elem","f64 GB/s");
+   for (nx,ny,nrpt) in sweep_sizes(options) {
+      let elements = (nrpt * nx * ny) as f64;
+      let f32_time = time_generic::<f32>(nrpt,nx,ny,options.timeout);
+      let f64_time = time_generic::<f64>(nrpt,nx,ny,options.timeout);
+      let (f32_time,f64_time) = match (f32_time,f64_time) {
+         (Some(f32_time),Some(f64_time)) => (f32_time,f64_time),
+         _ => {
+            println!("{:>8} {:>8} timed out",nx,ny);
+            continue;
+         }
+      };
+      println!("{:>8} {:>8} {:>12.4} {:>10.2} {:>12.4} {:>10.2}",nx,ny,
+                         f32_time / elements,2.0 * 4.0 * elements / f32_time,
+                         f64_time / elements,2.0 * 8.0 * elements / f64_time);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         C o m p a r e  L a y o u t s
+//
+//   Compares the nested Vec<Vec<f32>> layout, using crssub.rs, with the flat
+//   Vec<f32> layout, using crssub1d.rs, for the same array dimensions and
+//   input values. Each gets LAYOUT_WARMUPS warmup calls, made alternately,
+//   and then nrpt timed calls, again made alternately, so that neither
+//   layout is systematically measured earlier or later than the other.
+
+fn compare_layouts (options: &Options) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let mut nested_in = vec![vec![0.0f32; nx]; ny];
+   let mut nested_out = vec![vec![0.0f32; nx]; ny];
+   let mut flat_in = vec![0.0f32; nx * ny];
+   let mut flat_out = vec![0.0f32; nx * ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         nested_in[iy][ix] = (nx - ix + ny - iy) as f32;
+         flat_in[iy * nx + ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+   for _iwarm in 0..LAYOUT_WARMUPS {
+      crssub::csub(&nested_in,nx,ny,&mut nested_out);
+      crssub1d::csub1d(&flat_in,nx,ny,&mut flat_out);
+   }
+   let mut nested_times = Vec::with_capacity(nrpt);
+   let mut flat_times = Vec::with_capacity(nrpt);
+   for _irpt in 1..=nrpt {
+      let start = Instant::now();
+      crssub::csub(&nested_in,nx,ny,&mut nested_out);
+      nested_times.push(start.elapsed().as_nanos() as f64);
+      let start = Instant::now();
+      crssub1d::csub1d(&flat_in,nx,ny,&mut flat_out);
+      flat_times.push(start.elapsed().as_nanos() as f64);
+   }
+
+   //  Both layouts should give the same results, as expected.
+
+   'check_loop :
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = nested_in[iy][ix] + (ix + iy) as f32;
+         if nested_out[iy][ix] != expected || flat_out[iy * nx + ix] != expected {
+            println! ("Error {} {} {} {} {}",ix,iy,nested_out[iy][ix],
+                                         flat_out[iy * nx + ix],nested_in[iy][ix]);
+            break 'check_loop;
+         }
+      }
+   }
+   if nrpt > 0 {
+      print_summary("Nested Vec<Vec<f32>>",&mut nested_times);
+      print_summary("Flat Vec<f32>",&mut flat_times);
+      println! ("Ratio of medians, nested / flat, {:.3}",
+                  crstime::percentile(&nested_times,50.0) /
+                                     crstime::percentile(&flat_times,50.0));
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            R a n k  L a y o u t s
+//
+//   Implements --compare-layouts. Sets up the same input values as nested
+//   Vec<Vec<f32>>, flat Vec<f32>, Vec<Box<[f32]>> and, if nx is LAYOUT_COLUMNS,
+//   Vec<[f32; LAYOUT_COLUMNS]> arrays, and times the version of csub() for each
+//   in the same way as compare_layouts(): LAYOUT_WARMUPS warmup calls for each,
+//   then nrpt timed calls for each, the layouts taking turns. Then checks all
+//   the outputs and prints the layouts ranked by their median time, with the
+//   mean and its 95% confidence interval as given by print_summary().
+
+//  The call to the version of csub() for one of the layouts, with its arrays.
+
+type LayoutCall<'a> = Box<dyn FnMut() + 'a>;
+
+fn rank_layouts (options: &Options) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let nested_in: Vec<Vec<f32>> = (0..ny).map(|iy|
+                        (0..nx).map(|ix| (nx - ix + ny - iy) as f32).collect()).collect();
+   let mut nested_out = vec![vec![0.0f32; nx]; ny];
+   let flat_in: Vec<f32> = nested_in.concat();
+   let mut flat_out = vec![0.0f32; nx * ny];
+   let boxed_in: Vec<Box<[f32]>> =
+                     nested_in.iter().map(|row| row.clone().into_boxed_slice()).collect();
+   let mut boxed_out = vec![vec![0.0f32; nx].into_boxed_slice(); ny];
+   let use_arrays = nx == LAYOUT_COLUMNS;
+   let mut arrays_in: Vec<[f32; LAYOUT_COLUMNS]> = Vec::new();
+   let mut arrays_out: Vec<[f32; LAYOUT_COLUMNS]> = Vec::new();
+   if use_arrays {
+      for row in &nested_in {
+         let mut array = [0.0f32; LAYOUT_COLUMNS];
+         array.copy_from_slice(row);
+         arrays_in.push(array);
+      }
+      arrays_out = vec![[0.0f32; LAYOUT_COLUMNS]; ny];
+   } else {
+      println! ("Warning: Vec<[f32; N]> needs {} columns, not {}, so is left out",
+                                                                LAYOUT_COLUMNS,nx);
+   }
+
+   //  Each layout's call is a closure borrowing its own arrays, so they can
+   //  all be timed by the same loops. They are dropped before the outputs
+   //  are checked.
+
+   let mut names = Vec::new();
+   let mut times: Vec<Vec<f64>> = Vec::new();
+   {
+      let mut layouts: Vec<(&str,LayoutCall)> = vec![
+         ("Vec<Vec<f32>>",Box::new(|| crssub::csub(&nested_in,nx,ny,&mut nested_out))),
+         ("Vec<f32>",Box::new(|| crssub1d::csub1d(&flat_in,nx,ny,&mut flat_out))),
+         ("Vec<Box<[f32]>>",Box::new(|| crssub_boxed::csub(&boxed_in,nx,ny,&mut boxed_out))),
+      ];
+      if use_arrays {
+         layouts.push(("Vec<[f32; N]>",
+                  Box::new(|| crssub_vecarray::csub(&arrays_in,&mut arrays_out,ny))));
+      }
+      for _iwarm in 0..LAYOUT_WARMUPS {
+         for (_name,call) in layouts.iter_mut() {
+            call();
+         }
+      }
+      times.resize(layouts.len(),Vec::with_capacity(nrpt));
+      for _irpt in 1..=nrpt {
+         for (ilayout,(_name,call)) in layouts.iter_mut().enumerate() {
+            let start = Instant::now();
+            call();
+            times[ilayout].push(start.elapsed().as_nanos() as f64);
+         }
+      }
+      names.extend(layouts.iter().map(|(name,_call)| *name));
+   }
+
+   //  All the layouts should give the same results. Only the first error
+   //  for each is reported.
+
+   let mut errors = vec![false; names.len()];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = nested_in[iy][ix] + (ix + iy) as f32;
+         let mut values = vec![nested_out[iy][ix],flat_out[iy * nx + ix],boxed_out[iy][ix]];
+         if use_arrays {
+            values.push(arrays_out[iy][ix]);
+         }
+         for (ilayout,value) in values.iter().enumerate() {
+            if *value != expected && !errors[ilayout] {
+               println! ("Error {} {} {} {} {}",names[ilayout],ix,iy,value,expected);
+               errors[ilayout] = true;
+            }
+         }
+      }
+   }
+   if nrpt == 0 {
+      return;
+   }
+
+   let mut ranked: Vec<(usize,crstime::Summary)> =
+                   times.iter_mut().map(|t| crstime::summarise(t)).enumerate().collect();
+   ranked.sort_by(|a,b| a.1.median.total_cmp(&b.1.median));
+   let fastest = ranked[0].1.median;
+   let nelements = (nx * ny).max(1) as f64;
+   println! ("Layouts ranked by median time per call, {} rows of {} columns, {} calls:",
+                                                                        ny,nx,nrpt);
+   println! (" Rank  {:<16} {:>12} {:>12} {:>19} {:>10} {:>8} {:>7}",
+        "Layout","Median usec","Min usec","Mean usec, 95%","ns/elem","Ratio","CV %");
+   for (irank,(ilayout,summary)) in ranked.iter().enumerate() {
+      let cv = summary.coefficient_of_variation();
+      let mean = format!("{:.3} \u{b1} {:.3}",summary.mean / 1000.0,
+                                                  summary.confidence_95() / 1000.0);
+      println! (" {:>4}  {:<16} {:>12.3} {:>12.3} {:>19} {:>10.4} {:>8.3} {:>7.2}{}",
+                irank + 1,names[*ilayout],summary.median / 1000.0,summary.min / 1000.0,
+                mean,summary.median / nelements,summary.median / fastest,100.0 * cv,
+                if cv > CV_THRESHOLD { "  UNSTABLE" } else { "" });
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            T i m e  M a t r i x
+//
+//   Implements --matrix. For each size to be tested, sets up an input array
+//   and an output array for each of the versions of csub() in MATRIX_KERNELS,
+//   times them all in interleaved rounds, checks all the outputs, and records
+//   the median time for each. Then prints the table of times per element and
+//   the table of speedups relative to the first version, and writes the
+//   results to any files specified.
+
+fn time_matrix (options: &Options) {
+   let nkernels = MATRIX_KERNELS.len();
+   let mut rows: Vec<(usize,usize,Vec<f64>)> = Vec::new();
+   let mut records: Vec<crsresult::Record> = Vec::new();
+   for (nx,ny,nrpt) in sweep_sizes(options) {
+      let mut in_array = vec![vec![0.0f32; nx]; ny];
+      for (iy,row) in in_array.iter_mut().enumerate() {
+         for (ix,value) in row.iter_mut().enumerate() {
+            *value = (nx - ix + ny - iy) as f32;
+         }
+      }
+      let mut out_arrays = vec![vec![vec![-1.0f32; nx]; ny]; nkernels];
+      let round_rpt = (nrpt / MATRIX_ROUNDS).max(1);
+      let mut round_times = vec![Vec::with_capacity(MATRIX_ROUNDS); nkernels];
+      for _iround in 0..MATRIX_ROUNDS {
+         for (ikernel,name) in MATRIX_KERNELS.iter().enumerate() {
+            let nanosecs = time_named_kernel(name,round_rpt,&in_array,nx,ny,
+                                                        &mut out_arrays[ikernel]);
+            round_times[ikernel].push(nanosecs);
+         }
+      }
+      let options = Options { nx, ny, ..options.clone() };
+      let mut ns_per_element = Vec::with_capacity(nkernels);
+      for (ikernel,name) in MATRIX_KERNELS.iter().enumerate() {
+         if !check_output(&options,name,&in_array,&out_arrays[ikernel]) {
+            println! ("(Error was in the {} version)",name);
+         }
+         round_times[ikernel].sort_by(|a,b| a.total_cmp(b));
+         let median = crstime::percentile(&round_times[ikernel],50.0);
+         ns_per_element.push(median / (round_rpt * nx * ny).max(1) as f64);
+         records.push(crsresult::Record { kernel: name.to_string(),nx,ny,
+                nrpt: round_rpt,narrays: 1,nanosecs: median });
+      }
+      rows.push((nx,ny,ns_per_element));
+   }
+
+   print! ("Time per element, in nanoseconds:\n{:>8} {:>8}","Columns","Rows");
+   for name in MATRIX_KERNELS.iter() {
+      print! (" {:>9}",name);
+   }
+   println! ();
+   for (nx,ny,ns_per_element) in rows.iter() {
+      print! ("{:>8} {:>8}",nx,ny);
+      for value in ns_per_element.iter() {
+         print! (" {:>9.4}",value);
+      }
+      println! ();
+   }
+   print! ("Speedup relative to {}:\n{:>8} {:>8}",MATRIX_KERNELS[0],"Columns","Rows");
+   for name in MATRIX_KERNELS.iter() {
+      print! (" {:>9}",name);
+   }
+   println! ();
+   for (nx,ny,ns_per_element) in rows.iter() {
+      print! ("{:>8} {:>8}",nx,ny);
+      for value in ns_per_element.iter() {
+         print! (" {:>9.3}",ns_per_element[0] / value);
+      }
+      println! ();
+   }
+   write_records(options,&records);
+   if !options.gnuplot.is_empty() {
+      match crsresult::write_gnuplot(&options.gnuplot,MATRIX_KERNELS,&rows) {
+         Ok(()) => println!("Chart data written to {0}.dat, plot with 'gnuplot {0}.plt'",
+                                                                 options.gnuplot),
+         Err(error) => println!("Unable to write {}.dat or .plt: {}",options.gnuplot,error),
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       T i m e  N a m e d  K e r n e l
+//
+//   Times nrpt calls to the version of csub() with the given name, one of
+//   those in MATRIX_KERNELS, and returns the time taken in nanoseconds. Each
+//   version gets its own copy of time_calls(), so it can be inlined there.
+
+fn time_named_kernel (name: &str,nrpt: usize,in_array: &Vec<Vec<f32>>,
+                       nx: usize,ny: usize,out_array: &mut Vec<Vec<f32>>) -> f64 {
+   match name {
+      "iter" => time_calls(crssub_iter::csub,nrpt,in_array,nx,ny,out_array),
+      "unsafe" => time_calls(crssub_unsafe::csub,nrpt,in_array,nx,ny,out_array),
+      #[cfg(all(target_arch = "x86_64",feature = "prefetch"))]
+      "prefetch" => time_calls(crssub_prefetch::csub,nrpt,in_array,nx,ny,out_array),
+      "lenbound" => time_calls(crssub_lenbound::csub,nrpt,in_array,nx,ny,out_array),
+      "twopass" => time_calls(crssub_twopass::csub,nrpt,in_array,nx,ny,out_array),
+      "itercount" => time_calls(crssub_itercount::csub,nrpt,in_array,nx,ny,out_array),
+      "hint" => time_calls(crssub_hint::csub,nrpt,in_array,nx,ny,out_array),
+      "diagonal" => time_calls(crssub_diagonal::csub,nrpt,in_array,nx,ny,out_array),
+      "vertical" => time_calls(crssub_simd_vertical::csub,nrpt,in_array,nx,ny,out_array),
+      "morton" => time_calls(crssub_morton::csub,nrpt,in_array,nx,ny,out_array),
+      "rowptr" => time_calls(crssub_rowptr::csub,nrpt,in_array,nx,ny,out_array),
+      "recursive" => time_calls(crssub_recursive::csub,nrpt,in_array,nx,ny,out_array),
+      _ => time_calls(crssub::csub,nrpt,in_array,nx,ny,out_array),
+   }
+}
+
+fn time_calls<F> (kernel: F,nrpt: usize,in_array: &Vec<Vec<f32>>,
+                       nx: usize,ny: usize,out_array: &mut Vec<Vec<f32>>) -> f64
+                     where F: Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>) {
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      kernel (in_array,nx,ny,out_array);
+   }
+   start.elapsed().as_nanos() as f64
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        C o m p a r e  K e r n e l s
+//
+//   Used by the kernels that are also timed against other versions of csub().
+//   Times nrpt calls to each of the versions given, using the same input
+//   array, and prints the times and the ratio of the time for the first to
+//   the time for each of the others. Each version writes to a scratch array
+//   of its own, so the results of the main test are unaffected, and that
+//   array is then checked by check_output() using the name given, so any
+//   version that gets the wrong results is reported by name. Returns the
+//   times in nanoseconds, in the order the versions were given.
+
+//  A version of csub() to be timed by compare_kernels(), and its name.
+
+type NamedKernel<'a> = (&'a str,&'a dyn Fn(&Vec<Vec<f32>>,usize,usize,&mut Vec<Vec<f32>>));
+
+fn compare_kernels (options: &Options,in_array: &Vec<Vec<f32>>,
+                                           kernels: &[NamedKernel]) -> Vec<f64> {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let mut times = Vec::with_capacity(kernels.len());
+   for (name,kernel) in kernels {
+      let mut out_array = vec![vec![0.0f32; nx]; ny];
+      times.push(time_calls(kernel,nrpt,in_array,nx,ny,&mut out_array));
+      if nrpt > 0 && !check_output(options,name,in_array,&out_array) {
+         println! ("(Error was in the {} version)",name);
+      }
+   }
+   let timings: Vec<String> = kernels.iter().zip(times.iter())
+        .map(|((name,_),nanosecs)| format!("{} time {:.6} sec",name,nanosecs * 1.0e-9)).collect();
+   println! ("{}",timings.join(", "));
+   let ratios: Vec<String> = kernels.iter().zip(times.iter()).skip(1)
+        .map(|((name,_),nanosecs)| format!("{} / {} ratio {:.3}",kernels[0].0,name,
+                                                       times[0] / nanosecs)).collect();
+   if !ratios.is_empty() {
+      println! ("{}",ratios.join(", "));
+   }
+   times
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                   C o m p a r e  E l e m e n t  S i z e s
+//
+//   Times the generic version of csub() for u8, f32 and f64 elements, for
+//   the same array dimensions (or, with --sweep, the same range of array
+//   dimensions), and prints the time per element and the memory bandwidth
+//   for each, counting both the read and the write.
+
+fn compare_element_sizes (options: &Options) {
+   println!("{:>8} {:>8} {:>10} {:>9} {:>10} {:>9} {:>10} {:>9}","Columns","Rows",
+            "u8 ns/el","u8 GB/s","f32 ns/el","f32 GB/s","f64 ns/el","f64 GB/s");
+   for (nx,ny,nrpt) in sweep_sizes(options) {
+      let elements = (nrpt * nx * ny) as f64;
+      let u8_time = time_generic::<u8>(nrpt,nx,ny,options.timeout);
+      let f32_time = time_generic::<f32>(nrpt,nx,ny,options.timeout);
+      let f64_time = time_generic::<f64>(nrpt,nx,ny,options.timeout);
+      let (u8_time,f32_time,f64_time) = match (u8_time,f32_time,f64_time) {
+         (Some(u8_time),Some(f32_time),Some(f64_time)) => (u8_time,f32_time,f64_time),
+         _ => {
+            println!("{:>8} {:>8} timed out",nx,ny);
+            continue;
+         }
+      };
+      println!("{:>8} {:>8} {:>10.4} {:>9.2} {:>10.4} {:>9.2} {:>10.4} {:>9.2}",nx,ny,
+                         u8_time / elements,2.0 * 1.0 * elements / u8_time,
+                         f32_time / elements,2.0 * 4.0 * elements / f32_time,
+                         f64_time / elements,2.0 * 8.0 * elements / f64_time);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            T i m e  D t y p e
+//
+//   Times the generic version of csub() for the element type named by the
+//   --dtype option, and prints the time per element. The match selects one
+//   of the monomorphised copies of time_generic(), which sets up, times and
+//   checks arrays of that type.
+
+fn time_dtype (options: &Options) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let timeout = options.timeout;
+   let nanosecs = match options.dtype.as_str() {
+      "f32" => time_generic::<f32>(nrpt,nx,ny,timeout),
+      "f64" => time_generic::<f64>(nrpt,nx,ny,timeout),
+      "i32" => time_generic::<i32>(nrpt,nx,ny,timeout),
+      "u8" => time_generic::<u8>(nrpt,nx,ny,timeout),
+      _ => {
+         println!("Unknown element type {}, using f32",options.dtype);
+         time_generic::<f32>(nrpt,nx,ny,timeout)
+      }
+   };
+   let nanosecs = match nanosecs {
+      Some(nanosecs) => nanosecs,
+      None => {
+         println!("Timed out after {} seconds",timeout);
+         return;
+      }
+   };
+   println!("Time {:.6} sec, {:.4} ns per element",nanosecs * 1.0e-9,
+                                           nanosecs / (nrpt * nx * ny) as f64);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      C o m p a r e  S a t u r a t i o n
+//
+//   Times the saturating and wrapping integer versions of csub(), and checks
+//   the results of each. The input values are i32::MAX less the usual
+//   descending values, so the result before any overflow would be i32::MAX
+//   less (nx + ny) plus twice the index sum. That means the addition
+//   overflows for roughly the half of the array where ix + iy is more than
+//   (nx + ny) / 2, so both the overflowing and the normal cases are well
+//   represented.
+
+fn compare_saturation (options: &Options) {
+   let (nrpt,nx,ny) = (options.nrpt,options.nx,options.ny);
+   let mut in_array = vec![vec![0i32; nx]; ny];
+   let mut out_array = vec![vec![0i32; nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = i32::MAX - (nx - ix + ny - iy) as i32;
+      }
+   }
+   for saturating in [true,false] {
+      let name = if saturating { "Saturating" } else { "Wrapping" };
+      let start = Instant::now();
+      for _irpt in 1..=nrpt {
+         if saturating {
+            crssub_saturating::csub_saturating(&in_array,nx,ny,&mut out_array);
+         } else {
+            crssub_saturating::csub_wrapping(&in_array,nx,ny,&mut out_array);
+         }
+      }
+      let nanosecs = start.elapsed().as_nanos() as f64;
+      println!("{} time {:.6} sec, {:.4} ns per element",name,nanosecs * 1.0e-9,
+                                           nanosecs / (nrpt * nx * ny) as f64);
+
+      //  The expected value is worked out using 64-bit arithmetic, which
+      //  can't overflow, and then saturated or wrapped as appropriate.
+
+      if nrpt > 0 {
+         'check_loop :
+         for iy in 0..ny {
+            for ix in 0..nx {
+               let sum = in_array[iy][ix] as i64 + (ix + iy) as i64;
+               let expected = if saturating {
+                  sum.min(i32::MAX as i64) as i32
+               } else {
+                  sum as i32
+               };
+               if out_array[iy][ix] != expected {
+                  println! ("Error {} {} {} {}",
+                                 ix,iy,out_array[iy][ix],in_array[iy][ix]);
+                  break 'check_loop;
+               }
+            }
+         }
+      }
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          T i m e  G e n e r i c
+//
+//   Sets up input and output arrays of elements of type T, with ny rows of
+//   nx columns, times nrpt calls to the generic csub(), checks the results,
+//   and returns the time taken in nanoseconds. If timeout is non-zero and
+//   the calls take longer than that many seconds, they are abandoned and
+//   None is returned.
+
+fn time_generic<T: Element> (nrpt: usize,nx: usize,ny: usize,timeout: f64)
+                                                              -> Option<f64> {
+   let mut in_array = vec![vec![T::from_index(0); nx]; ny];
+   let mut out_array = vec![vec![T::from_index(0); nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = T::from_index(nx - ix + ny - iy);
+      }
+   }
+   let watchdog = Watchdog::start(timeout,false);
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      if watchdog.is_some() && TIMED_OUT.load(atomic::Ordering::Relaxed) {
+         return None;
+      }
+      crssub_generic::csub (&in_array,nx,ny,&mut out_array);
+   }
+   let elapsed = start.elapsed().as_nanos() as f64;
+   drop(watchdog);
+   'check_loop :
+   for iy in 0..ny {
+      for ix in 0..nx {
+         if out_array[iy][ix] != (in_array[iy][ix] + T::from_index(ix + iy)) {
+            println! ("Error {} {} {} {}",
+                           ix,iy,out_array[iy][ix],in_array[iy][ix]);
+            break 'check_loop;
+         }
+      }
+   }
+   Some(elapsed)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      F l u s h  F r o m  C a c h e
+//
+//   Makes sure that none of the elements of an array are held in the cache.
+//   On x86_64 this uses the clflush instruction on each cache line of each
+//   row. Elsewhere, it has to fall back on reading through a buffer larger
+//   than any likely cache, which should push everything else out.
+
+#[cfg(target_arch = "x86_64")]
+fn flush_from_cache (array: &[Vec<f32>]) {
+   use std::arch::x86_64::{_mm_clflush,_mm_mfence};
+   for row in array {
+      let bytes = std::mem::size_of_val(&row[..]);
+      let start = row.as_ptr() as *const u8;
+      let mut offset = 0;
+      while offset < bytes {
+         unsafe { _mm_clflush(start.add(offset)); }
+         offset += 64;
+      }
+      if bytes > 0 {
+         unsafe { _mm_clflush(start.add(bytes - 1)); }
+      }
+   }
+   unsafe { _mm_mfence(); }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn flush_from_cache (_array: &[Vec<f32>]) {
+   let buffer = vec![1u8; 64 * 1024 * 1024];
+   let mut total: u64 = 0;
+   for index in (0..buffer.len()).step_by(64) {
+      total += unsafe { std::ptr::read_volatile(&buffer[index]) } as u64;
+   }
+   std::hint::black_box(total);
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The code checks that the command line arguments are valid numbers, but
+     doesn't check that they're not zero. It is only a test routine. It only
+     checks they're valid numbers because I was trying to understand the
+     way to do that, using match to check the parse() result.
+
+   o The code can be made to run faster by using a 1D array and doing the
+     index calculations in the code, but that seems to defeat the point of
+     this test. (Indeed, I've seen comments that suggest this as the best
+     way to speed up 2D array access in Rust. Perhaps this will change as
+     the compiler matures, as it did with Swift.)
+
+   o With the default sizes, the two arrays take up 160 KBytes, which will
+     fit in the L2 cache of most modern machines, so the test is really one
+     of cache access rather than main memory access. Simply making the arrays
+     bigger changes the shape of the problem as well as the working set.
+     The --arrays option lets the working set be pushed out of the cache
+     while keeping the individual array dimensions fixed.
+
+   o With --latency, the time for each call includes the cost of bringing
+     all the data in from main memory, which the normal test will usually
+     avoid. The flushing and the individual timing take far longer than the
+     calls themselves, so it makes no sense to time the program as a whole
+     in this mode. On anything other than x86_64, the flushing is done by
+     reading a large buffer, which is slow and less certain to work.
+
+   o An f64 array moves twice as many bytes as an f32 array with the same
+     number of elements. If the test is limited by memory bandwidth, as it
+     should be once the arrays are too big for the cache, the f64 version
+     should manage about half as many elements per second as the f32 version
+     but about the same number of GBytes per second. The --precision option
+     with --sweep shows this directly.
+
+   o In principle, since the input array never changes, a clever enough
+     compiler could work out that all but the last of the nrpt calls have
+     no effect, and skip them. As far as I can tell, rustc doesn't do this,
+     probably because csub() writes through a mutable reference and the
+     compiler doesn't look that far, but there is no guarantee that a later
+     version won't. --repeat-pattern makes sure each call is different, at
+     the cost of one extra store per call, which makes the timings more
+     trustworthy. If the timings with and without it differ by more than
+     that small cost, something is being optimised away.
+
+   o --seed-per-repeat is a stronger version of --repeat-pattern: rather
+     than one element, a whole row changes before each call, and to values
+     that can't be predicted from the repeat number. Reseeding from the
+     repeat number, rather than carrying on with one generator, keeps the
+     refills reproducible and means the separate timing of the refills does
+     exactly the same work as the refills made during the test.
+
+   o --burn-in has to be longer than it might seem. A processor that has
+     been idle can take tens of milliseconds to reach its full clock speed
+     once it is given work, and a laptop on battery may take longer or never
+     get there at all. On the machine used for testing - a virtual machine,
+     which hides what the host's processors are doing from it - the first
+     and last burn-in calls took the same time to within a few percent, so
+     the option made no visible difference there, and its effect needs to be
+     judged on the machine being measured. It isn't a substitute for
+     setting the frequency governor to 'performance' where that's possible.
+
+   o --affinity-sweep times each call individually, like --raw-timings, so
+     that a median can be taken and isn't thrown by the odd call that is
+     interrupted. On a machine with many processors it can take a while,
+     as it makes nrpt calls on each of them; a smaller repeat count is
+     usually enough. Remember that an unpinned run can be moved between
+     processors by the scheduler at any time, so its timings may be a mix
+     of those for different processors.
+
+   o --page-faults shows that, for these programs, there is almost no
+     first-touch cost left by the time csub() is called. Even with arrays of
+     5000 by 5000 elements, each 100 MBytes, the first call takes only one
+     or two minor page faults and about the same time as the second. The
+     input arrays have been touched by being filled with their values, and
+     so, less obviously, have the output arrays: vec![row; ny] clones the
+     row it is given to make all but the last of the rows, and cloning
+     writes every element. Only the one row that isn't a clone is left
+     untouched. The faults are all taken while the arrays are set up, which
+     is outside the timings anyway. An array allocated in one piece with
+     vec![0.0; n] would be different, since that gets zeroed pages straight
+     from the operating system, which aren't mapped until first used.
+
+   o With --arrays, the warmup calls cycle through the pairs of arrays, but
+     two successive calls are to different arrays, and the times may not
+     settle until every array has been touched at least once. Setting the
+     maximum number of warmups to several times the number of arrays is a
+     good idea. Very small arrays can take so little time that the timer
+     resolution dominates, and then the times may be stable from the start,
+     or may never look stable, which is why there is a limit.
+
+   o The all-elements loop in --row-headers adds into a row of column
+     totals, rather than into a single sum. The compiler may not reorder
+     floating point additions, so a single sum can't be vectorised, and
+     would have measured the latency of the additions rather than the cost
+     of reading the data. With small arrays the row headers will all be in
+     the cache, and the percentage reported is mainly the cost of the extra
+     load and loop overhead for each row; with large arrays, or --arrays,
+     cache misses on the row headers start to count as well.
+
+   o --endian=big reads the same byte order as the data in a FITS image,
+     but --input expects nothing but the data values. A FITS file starts
+     with a header, a whole number of 2880-byte blocks, and only images
+     with BITPIX = -32 hold 32-bit floating point values, so the data has
+     to be extracted from the file first - for example using dd with the
+     right offset - and nx and ny set from NAXIS1 and NAXIS2.
+
+   o Each output element for the usual input values is nx + ny exactly, an
+     integer small enough to be held exactly as an f32 for any sensible
+     array size, and the elements are added up as f64 values, so the
+     reference sum should match exactly, not just within the tolerance
+     allowed. The check can't tell where an error is, or catch errors that
+     cancel out, but it only needs the output arrays and the dimensions.
+
+   o --exclusive uses the file locking in the standard library, which needs
+     Rust 1.89 or later, and is an advisory lock - it only keeps out other
+     programs that ask for the same lock, so it only helps if all the tests
+     run on a machine use it. Nothing is ever written to the lock file, and
+     it is left in place afterwards, since removing it could let a waiting
+     program and a new one both think they had the lock.
+
+   o available_parallelism() was originally just unwrapped with a default
+     of 1, which was safe, but gave no hint of why the threads kernel was
+     only using one thread. default_threads(), now in crssub_threads.rs, is
+     passed the result rather than getting it itself, so its doctest, run
+     through crslib.rs, can check that a simulated failure gives 1 thread
+     and that a real count is used as it is.
+     The warning is only given when the thread count is going to be used,
+     so runs in a restricted container that don't use threads aren't
+     cluttered with it.
+
+   o --matrix splits the calls for each size into rounds, rather than
+     making all the calls for one version and then all those for the next,
+     because the state of the machine drifts - the clock speed changes as
+     the processor warms up, for example - and making all the calls for one
+     version together would give whichever came first an advantage. The
+     first round also serves as a warmup for every version. The input array
+     is shared by all the versions, so it is in the same place in memory for
+     each of them, but each has its own output array, so all the outputs
+     can be checked at the end.
+
+   o --all times each call separately, rather than timing a run of calls as
+     the normal test does, since it is the alternation that matters. The
+     overhead of reading the clock, some tens of nanoseconds, is included
+     in every time, so for very small arrays the medians will be closer
+     together than the real difference between the layouts.
+
+   o Wherever the distribution of the times of individual calls is printed
+     - with --latency, --raw-timings and --all - the coefficient of
+     variation, the standard deviation divided by the mean, is printed too,
+     and if it is over 5% the measurement is flagged as unstable. A few
+     very slow calls, caused by interrupts or other programs, are enough to
+     do this, so it is worth comparing the mean with the median, which is
+     much less affected by them.
+
+   o --elemsize only shows the memory-bound pattern - similar bandwidths,
+     with times per element in proportion to the element sizes - when the
+     arrays are too big for the caches and the loop is fast enough to keep
+     up with memory. With rustc 1.95 on x86_64, the bounds checks keep the
+     loop scalar, at about a nanosecond per element whatever the type, and
+     that is the limit rather than memory, even for 2000 by 2000 arrays. In
+     that case the figures say that the loop overhead dominates, which is
+     itself worth knowing.
+
+   o The watchdog used by --timeout spends the test asleep in recv_timeout(),
+     so it takes no processor time from the test. Where a timing can be
+     abandoned, time_generic() checks a flag before each call to csub(),
+     which is a single relaxed load of a value that stays in the cache and
+     shouldn't be measurable against a call that processes a whole array.
+     This does mean a single very large call can't be interrupted, so the
+     time limit can be overrun by up to one call's worth of time.
+
+   o The --roofline figures assume 8 bytes are moved per element, 4 read
+     and 4 written. Most processors actually read each cache line of the
+     output array before writing to it (a 'write allocate'), so the real
+     traffic is nearer 12 bytes per element, and 67% of peak may be as good
+     as it gets. If the arrays fit in the cache, the figures can exceed
+     100%, which just says that main memory bandwidth isn't the limit.
+
+   o --numa-first-touch only makes a difference on a machine with more than
+     one NUMA node - typically a server with more than one processor socket
+     - and only for the threads kernel. The rows of a vector of vectors are
+     separate allocations, so each has to be allocated by the thread that
+     will use it, not just written to by it: vec![row; ny] clones the
+     first row on the calling thread, which would touch every page there.
+
+   o For views of sections of larger arrays, see crssub1d_view.rs and
+     crsmain1d_view.rs.
+
+   o --sibling-load was written on a virtual machine with a single processor
+     and no SMT, where all it can do is report that there is no sibling. To
+     check the rest of it, a copy was built that treated processor 0 as its
+     own sibling; the two threads then simply share the processor, and the
+     test took just over twice as long with the load, as it should. Real
+     figures need a machine with hyperthreading, where the slowdown should
+     be rather less than that. The test and the load are always on the same
+     core, but nothing is done to keep other programs off it.
+
+   o --zero-output costs less than might be expected when the arrays fit in
+     the cache, since the memset() that clears a row is about as fast as
+     anything can be. With rustc 1.95 and -C target-cpu=native it added
+     about 4% to the time for crssub.rs and about 10% for the unsafe version,
+     for the default 10 rows of 2000 columns. 1000 by 1000 arrays no longer
+     fit in the level 2 cache, and then it added about 12% and over 30%
+     respectively: the faster the version, the more the extra pass through
+     the output shows.
+
+   o None of the kernels sets off the MIN_NS_PER_ELEMENT warning with rustc
+     1.95; the fastest, memcpy, took about 0.05 ns per element for 10 rows
+     of 200 columns, five times the floor. The output arrays are checked
+     once the calls are over, so the compiler can't remove the calls
+     altogether, but it would be entitled to make just the last call with
+     each pair of arrays, since none of the earlier results are looked at,
+     and a change to the inlining could one day let it. The warning is for
+     that, and for anyone adapting the program who doesn't check the output.
+
+   o --runs uses the median of each run rather than the mean, so that the
+     odd interrupted call, which is what mostly makes up the variation
+     within a run, doesn't count as drift. On an otherwise idle test machine
+     the two are very different: for 5 runs of 1000 calls on 200 by 200
+     arrays the variation within a run was anything from 4% to 44%, but the
+     medians were within 0.14% of each other, and for 2000 by 2000 arrays
+     about 7% against 0.6%. A run of a few seconds on a quiet machine is
+     representative, in other words; drift should show up on a laptop, or
+     on a machine shared with other work.
+
+   o --baseline compares the time per element, like --compare-csv, so the
+     baseline can have been run with a different number of calls. Running
+     the same test twice in a row with 10000 calls on 10 rows of 2000
+     columns gave changes of about 1% to 3% either way, which is normal run
+     to run variation and why REGRESSION_THRESHOLD is as high as 5%. The
+     result of the test itself isn't added to the baseline file unless --csv
+     names the same file, in which case it is written after the comparison,
+     so a test is never compared with itself.
+
+   o --bench-json writes the file hyperfine would, but the times in it are
+     for a single call of csub(), timed inside the program, where hyperfine's
+     are for a whole run of a process. For the default 10 rows of 2000
+     columns, a call takes about 20 microseconds, so the two sets of figures
+     should never be put on the same axis without saying which is which; the
+     command field, which is the full command line, at least shows they
+     come from crsmain. Python's json module reads the file as it is.
 
 */
@@ -1,6 +1,22 @@
 use std::env;
+use std::process;
 
-mod crssub1d;
+//  Unlike the other test programs, this one uses the routines from the
+//  library built from crslib.rs, rather than including them with mod
+//  declarations, so it shows the library working as a dependency. The
+//  library has to be built first:
+//
+//     rustc -O --crate-type=rlib crslib.rs
+//     rustc -O -L . crsmain1d.rs
+
+extern crate crslib;
+
+use crslib::CsubError;
+
+//  The exit status used if either array dimension is zero, so there is
+//  nothing to verify, as in crsmain.rs.
+
+const EMPTY_ARRAY_STATUS: i32 = 2;
 
 fn main() {
     let mut nrpt = 100;
@@ -27,9 +43,6 @@ fn main() {
     }
     println!("{} {} {}",nrpt,rows,cols);
 
-    assert_ne!(rows, 0, "rows were zero");
-    assert_ne!(cols, 0, "cols were zero");
-
     let mut in_array = vec![0.0f32; cols * rows];
     let mut out_array = vec![0.0f32; cols * rows];
     for iy in 0..rows {
@@ -38,12 +51,33 @@ fn main() {
        }
     }
 
+   //  The first call uses the checked version of csub1d(), which returns an
+   //  error - for example if either dimension is zero - rather than panicking.
+   //  The remaining calls use the normal version. An empty array isn't
+   //  treated as a failure here, since it is reported below.
+
    println! ("Calling");
-    for _irpt in 1..=nrpt {
-       crssub1d::csub1d (&mut in_array,cols,rows,&mut out_array);
+    if nrpt > 0 {
+       if let Err(error) = crslib::csub_flat_checked (&in_array,cols,rows,&mut out_array) {
+          println! ("Error: {}",error);
+          if error != CsubError::EmptyArray {
+             process::exit(1);
+          }
+       }
+    }
+    for _irpt in 2..=nrpt {
+       crslib::csub_flat (&mut in_array,cols,rows,&mut out_array);
     }
     println! ("Called");
 
+   //  With either dimension zero the check loop would do nothing and report
+   //  no errors, which would look like success.
+
+    if rows == 0 || cols == 0 {
+       println! ("Empty array, nothing to verify");
+       process::exit(EMPTY_ARRAY_STATUS);
+    }
+
     'check_loop :
     for iy in 0..rows {
        for ix in 0..cols {
@@ -0,0 +1,222 @@
+//
+//                 c r s m a i n 1 d _ c o n v e r t . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, timing conversions between f32 and f64.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version times the two routines in crssub1d_convert.rs, which work on
+//    arrays held as single 1D arrays, with an output array of a different type
+//    from the input: csub_convert(), which reads f32 and writes f64, and
+//    csub_narrow(), which reads f64 and writes f32. They are compared with
+//    csub1d() in crssub1d.rs, which reads and writes f32, and with the same loop
+//    for f64 throughout, so the difference isolates the cost of the conversion.
+//    It first checks that all four give the same values, then times each of them,
+//    and reports the times relative to csub1d(). Finally, it does the same for
+//    csub_i16(), which reads 16-bit integers and writes f32.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain1d_convert.rs
+//
+// Invocation:
+//    ./crsmain1d_convert irpt ny nx
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added the timing of csub_i16().
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+mod crssub1d;
+mod crssub1d_convert;
+mod crserror;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   //  Set up the input arrays, one of each type, with the usual descending
+   //  values, which are whole numbers and so exact in either type.
+
+   let mut in_f32 = vec![0.0f32; nx * ny];
+   let mut in_f64 = vec![0.0f64; nx * ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_f32[iy * nx + ix] = (nx - ix + ny - iy) as f32;
+         in_f64[iy * nx + ix] = (nx - ix + ny - iy) as f64;
+      }
+   }
+
+   //  Check the widened and narrowed values against csub1d(). The results
+   //  are whole numbers too, so should convert exactly, and each output
+   //  array starts off different, so any element a version fails to set
+   //  shows up.
+
+   let mut out_f32 = vec![0.0f32; nx * ny];
+   crssub1d::csub1d (&mut in_f32,nx,ny,&mut out_f32);
+   let mut widened = vec![-1.0f64; nx * ny];
+   crssub1d_convert::csub_convert (&in_f32,nx,ny,&mut widened);
+   let mut narrowed = vec![-2.0f32; nx * ny];
+   crssub1d_convert::csub_narrow (&in_f64,nx,ny,&mut narrowed);
+   let mut out_f64 = vec![-3.0f64; nx * ny];
+   csub1d_f64 (&in_f64,nx,ny,&mut out_f64);
+   for i in 0..nx * ny {
+      let reference = out_f32[i];
+      if widened[i] != reference as f64 || narrowed[i] != reference
+                                              || out_f64[i] != reference as f64 {
+         println! ("Error: element {} is {} widened, {} narrowed, {} as f64, \
+                   csub1d() gives {}",i,widened[i],narrowed[i],out_f64[i],reference);
+         process::exit(1);
+      }
+   }
+
+   //  Time each version in turn.
+
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d::csub1d (&mut in_f32,nx,ny,&mut out_f32);
+   }
+   let same_f32 = start.elapsed().as_secs_f64();
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      csub1d_f64 (&in_f64,nx,ny,&mut out_f64);
+   }
+   let same_f64 = start.elapsed().as_secs_f64();
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d_convert::csub_convert (&in_f32,nx,ny,&mut widened);
+   }
+   let widen = start.elapsed().as_secs_f64();
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d_convert::csub_narrow (&in_f64,nx,ny,&mut narrowed);
+   }
+   let narrow = start.elapsed().as_secs_f64();
+   println!("f32 to f32 {:.6} sec, f64 to f64 {:.6} sec ({:.2})",
+                                            same_f32,same_f64,same_f64 / same_f32);
+   println!("f32 to f64 {:.6} sec ({:.2}), f64 to f32 {:.6} sec ({:.2})",
+                                 widen,widen / same_f32,narrow,narrow / same_f32);
+
+   //  The 16-bit integer input. The usual values only fit for arrays of up
+   //  to 32767 in nx + ny, so they wrap round beyond that. The reference is
+   //  csub1d() working on the same values as f32.
+
+   let in_i16: Vec<i16> = (0..nx * ny)
+               .map(|i| ((nx - i % nx + ny - i / nx) % 32768) as i16).collect();
+   let in_converted: Vec<f32> = in_i16.iter().map(|&value| value as f32).collect();
+   let mut reference = vec![0.0f32; nx * ny];
+   crssub1d::csub1d (&in_converted,nx,ny,&mut reference);
+   let mut from_i16 = vec![-1.0f32; nx * ny];
+   crssub1d_convert::csub_i16 (&in_i16,nx,ny,&mut from_i16);
+   if let Some(i) = (0..nx * ny).find(|&i| from_i16[i] != reference[i]) {
+      println! ("Error: element {} is {} from i16, csub1d() gives {}",i,from_i16[i],
+                                                                      reference[i]);
+      process::exit(1);
+   }
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d_convert::csub_i16 (&in_i16,nx,ny,&mut from_i16);
+   }
+   let integer = start.elapsed().as_secs_f64();
+   println!("i16 to f32 {:.6} sec ({:.2})",integer,integer / same_f32);
+}
+
+//  csub1d_f64() is the same loop as csub1d() in crssub1d.rs, but for f64
+//  throughout, and is the same-type comparison for the double precision
+//  side of the conversions.
+
+fn csub1d_f64 (input_array: &[f64],nx: usize,ny: usize,output_array: &mut [f64]) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy * nx + ix] = input_array[iy * nx + ix] + (ix + iy) as f64;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o On the test machine, with rustc 1.95, for the default 10 rows of 2000
+     columns, f64 to f64 took about 1.8 times as long as f32 to f32, and the
+     two conversions about 1.7 times as long (f32 to f64) and 1.6 times as
+     long (f64 to f32). For arrays too big for the cache - 2000 by 4000 -
+     the figures were 2.6, 1.9 and 1.7. A conversion moves 12 bytes for each
+     element, compared with 8 for f32 throughout and 16 for f64, and the
+     times fall between the two same-type times in much that proportion, so
+     the conversion itself costs little compared with the memory traffic.
+
+   o Reading i16 instead of f32 takes that further. For the default array
+     size, which fits in the cache, i16 to f32 took about 1.2 to 1.3 times
+     as long as f32 to f32: the extra instructions for the conversion are
+     all there is to see. For arrays too big for the cache the order is
+     reversed, with ratios of 0.78 for 2000 by 4000 and 0.66 for 100 rows of
+     40000 columns, since each element now moves 6 bytes rather than 8. So
+     for real detector data, which is mostly too big for the cache, it is
+     well worth keeping the data in its raw 16-bit form until it is used.
+
+   o The f64 to f64 loop is kept here, rather than in a module of its own,
+     since nothing else uses it.
+
+*/
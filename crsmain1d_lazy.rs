@@ -0,0 +1,169 @@
+//
+//                    c r s m a i n 1 d _ l a z y . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, comparing a lazy iterator with csub1d().
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version compares the csub1d() routine in crssub1d.rs, which writes
+//    its results to an output array as it goes, with the csub_iter() routine in
+//    crssub1d_lazy.rs, which returns an iterator that produces the results on
+//    demand. It first checks that collecting the iterator's results into a
+//    vector gives exactly the same values as csub1d(). It then times the eager
+//    csub1d(), the iterator collected into an existing vector each time, and
+//    the iterator just summed, with no output array at all.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain1d_lazy.rs
+//
+// Invocation:
+//    ./crsmain1d_lazy irpt ny nx
+//
+//    where:
+//      irpt  is the number of times each routine is called - default 100000.
+//      ny    is the number of rows in the array tested - default 10.
+//      nx    is the number of columns in the array tested - default 2000.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+mod crssub1d;
+mod crssub1d_lazy;
+mod crserror;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   //  Set up the input and output arrays, and set the input values, using
+   //  the usual descending values.
+
+   let mut in_array = vec![0.0f32; nx * ny];
+   let mut out_array = vec![0.0f32; nx * ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy * nx + ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+
+   //  Check that the iterator produces exactly the same values as csub1d().
+
+   crssub1d::csub1d (&mut in_array,nx,ny,&mut out_array);
+   let collected: Vec<f32> = crssub1d_lazy::csub_iter(&in_array,nx,ny).collect();
+   if collected != out_array {
+      println! ("Error: the iterator's values differ from those from csub1d()");
+      process::exit(1);
+   }
+
+   //  Time csub1d() itself, then the iterator collected into the existing
+   //  output array - clear() keeps the allocation, so no memory is allocated
+   //  after the first time - and then the iterator simply summed.
+
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d::csub1d (&mut in_array,nx,ny,&mut out_array);
+   }
+   let eager = start.elapsed().as_secs_f64();
+   let mut collected: Vec<f32> = Vec::with_capacity(nx * ny);
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      collected.clear();
+      collected.extend(crssub1d_lazy::csub_iter(&in_array,nx,ny));
+   }
+   let lazy = start.elapsed().as_secs_f64();
+   let mut total = 0.0f64;
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      total += crssub1d_lazy::csub_iter(&in_array,nx,ny).sum::<f32>() as f64;
+   }
+   let summed = start.elapsed().as_secs_f64();
+   println!("csub1d() {:.6} sec, iterator collected {:.6} sec, summed {:.6} sec",
+                                                           eager,lazy,summed);
+   println!("(Total of the sums {})",total);
+   if collected != out_array {
+      println! ("Error: the collected values differ from those from csub1d()");
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The sum is printed so the optimiser can't decide it isn't needed and
+     skip the summing loop altogether. Summing f32 values one after another
+     can't be vectorised, since the compiler isn't allowed to reorder the
+     additions, so the summed time is mostly the time for the additions
+     rather than for working through the array.
+
+   o With rustc 1.95 on x86_64, collecting the iterator takes about fifteen
+     times as long as csub1d() for the default array size. The iterator is
+     built using flat_map(), and a flat_map() can't say in advance exactly
+     how many values it will produce, so extend() has to check there is
+     room for each value as it is pushed, and the loop isn't vectorised.
+     This is the sort of overhead the comparison is meant to show up; a
+     lazy iterator is convenient, but it isn't free.
+
+*/
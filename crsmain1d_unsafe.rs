@@ -0,0 +1,181 @@
+//
+//                  c r s m a i n 1 d _ u n s a f e . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, using unsafe code on a 1D array.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version uses the csub1d() routine in crssub1d_unsafe.rs, which holds
+//    each 2D array as a single flat 1D array and accesses it using unsafe code,
+//    with just one check at the start that the arrays are large enough. Once
+//    the timed calls have been made and the results checked, it also checks
+//    that this upfront assertion really does fire when csub1d() is passed an
+//    array that is too small.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain1d_unsafe.rs
+//
+// Invocation:
+//    ./crsmain1d_unsafe irpt ny nx
+//
+//    where:
+//      irpt  is the number of times the subroutine is called - default 100000.
+//      ny    is the number of rows in the array tested - default 10.
+//      nx    is the number of columns in the array tested - default 2000.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::panic;
+use std::process;
+use std::time::Instant;
+
+mod crssub1d_unsafe;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   //  Set up the input and output arrays, and set the input values, using
+   //  the usual descending values.
+
+   let mut in_array = vec![0.0f32; nx * ny];
+   let mut out_array = vec![0.0f32; nx * ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy * nx + ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+
+   //  Repeat the call to the manipulating subroutine.
+
+   let start = Instant::now();
+   for _irpt in 1..=nrpt {
+      crssub1d_unsafe::csub1d (&in_array,nx,ny,&mut out_array);
+   }
+   println!("Time {:.6} sec",start.elapsed().as_secs_f64());
+
+   //  Check that we got the expected results.
+
+   let mut errors = 0;
+   'check_loop :
+   for iy in 0..ny {
+      for ix in 0..nx {
+         if out_array[iy * nx + ix] != (in_array[iy * nx + ix] + (ix + iy) as f32) {
+            println! ("Error {} {} {} {}",ix,iy,out_array[iy * nx + ix],
+                                                         in_array[iy * nx + ix]);
+            errors += 1;
+            break 'check_loop;
+         }
+      }
+   }
+
+   //  Now check that the assertion in csub1d() catches undersized arrays,
+   //  first an input array and then an output array one element short.
+
+   if nx > 0 && ny > 0 {
+      if !assertion_fires(nx * ny - 1,nx * ny,nx,ny) {
+         println! ("Error: undersized input array not detected");
+         errors += 1;
+      }
+      if !assertion_fires(nx * ny,nx * ny - 1,nx,ny) {
+         println! ("Error: undersized output array not detected");
+         errors += 1;
+      }
+   }
+   if errors > 0 {
+      process::exit(1);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                       A s s e r t i o n  F i r e s
+//
+//   Calls csub1d() with input and output arrays of the given lengths, which
+//   are expected to be too small for the given dimensions. Returns true if
+//   csub1d() panics, as it should. The default panic message is suppressed
+//   while this is done, since here a panic is the expected outcome.
+
+fn assertion_fires (in_len: usize,out_len: usize,nx: usize,ny: usize) -> bool {
+   let in_array = vec![0.0f32; in_len];
+   let mut out_array = vec![0.0f32; out_len];
+   let default_hook = panic::take_hook();
+   panic::set_hook(Box::new(|_info| {}));
+   let result = panic::catch_unwind(move || {
+      crssub1d_unsafe::csub1d (&in_array,nx,ny,&mut out_array);
+   });
+   panic::set_hook(default_hook);
+   result.is_err()
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The assertion check needs both nx and ny to be at least 1, so that an
+     array one element short can be made. With either dimension zero there
+     is nothing for csub1d() to access, and no array can be too small, so
+     the check is skipped.
+
+*/
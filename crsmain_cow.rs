@@ -0,0 +1,194 @@
+//
+//                       c r s m a i n _ c o w . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, using a Cow input array.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version calls the csub_cow() routine in crssub_cow.rs, which takes
+//    its input array as a Cow (a 'clone on write' value) and returns its output
+//    array. If passed an owned array, csub_cow() modifies it in place and
+//    returns it without doing any allocation; if passed a borrowed array, it
+//    has to allocate a new one. This program first checks that both of these
+//    cases give the right answer - and that the owned case really does reuse
+//    the array - and then times each case.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_cow.rs
+//
+// Invocation:
+//    ./crsmain_cow irpt nx ny
+//
+//    where:
+//      irpt  is the number of times the subroutine is called - default 100000.
+//      nx    is the number of columns in the array tested - default 2000.
+//      ny    is the number of rows in the array tested - default 10.
+//
+//    (As in crsmain.rs, the second argument actually sets the number of rows
+//    and the third the number of columns.)
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::borrow::Cow;
+use std::env;
+use std::time::Instant;
+
+mod crssub_cow;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default values
+   //  or values supplied on the command line, just as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   //  Set up the input array, with the usual descending values.
+
+   let mut in_array = vec![0.0f32; nx * ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy * nx + ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+
+   //  First check the borrowed case. This must leave the input array alone
+   //  and return a new array with the right values in it.
+
+   let saved_array = in_array.clone();
+   let out_array = crssub_cow::csub_cow(Cow::Borrowed(&in_array),nx,ny);
+   if in_array != saved_array {
+      println!("Error: borrowed input array was modified");
+   }
+   check_result("borrowed",&in_array,&out_array,nx,ny);
+
+   //  Then the owned case. Here the array returned should be the array that
+   //  was passed, which can be checked by comparing the addresses of their
+   //  data.
+
+   let owned_array = in_array.clone();
+   let owned_address = owned_array.as_ptr();
+   let out_array = crssub_cow::csub_cow(Cow::Owned(owned_array),nx,ny);
+   if out_array.as_ptr() != owned_address {
+      println!("Error: owned input array was not reused");
+   }
+   check_result("owned",&in_array,&out_array,nx,ny);
+
+   //  Now time the two cases. The borrowed case allocates a new output array
+   //  on each call. The owned case passes the output of each call back in as
+   //  the input to the next, the way a pipeline would, so no allocation is
+   //  needed at all. (The values carry on growing, of course, but that has
+   //  no effect on the time taken.)
+
+   let start = Instant::now();
+   let mut total = 0.0f32;
+   for _irpt in 1..=nrpt {
+      let out_array = crssub_cow::csub_cow(Cow::Borrowed(&in_array),nx,ny);
+      total += out_array[0];
+   }
+   let borrowed_time = start.elapsed().as_nanos() as f64;
+
+   let start = Instant::now();
+   let mut data_array = in_array.clone();
+   for _irpt in 1..=nrpt {
+      data_array = crssub_cow::csub_cow(Cow::Owned(data_array),nx,ny);
+   }
+   let owned_time = start.elapsed().as_nanos() as f64;
+   total += data_array[0];
+
+   let elements = (nrpt * nx * ny).max(1) as f64;
+   println!("Borrowed input: {:.4} ns per element",borrowed_time / elements);
+   println!("Owned input:    {:.4} ns per element",owned_time / elements);
+   println!("(Ignore this - it just makes sure the results are used: {})",total);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          C h e c k  R e s u l t
+//
+//   Checks that an output array returned by csub_cow() has the expected
+//   values, given the input array, printing a message if it doesn't.
+
+fn check_result (case: &str,in_array: &[f32],out_array: &[f32],nx: usize,ny: usize) {
+   if out_array.len() != nx * ny {
+      println!("Error: {} case returned {} elements",case,out_array.len());
+      return;
+   }
+   'check_loop :
+   for iy in 0..ny {
+      for ix in 0..nx {
+         if out_array[iy * nx + ix] != (in_array[iy * nx + ix] + (ix + iy) as f32) {
+            println! ("Error in {} case {} {} {} {}",case,ix,iy,
+                              out_array[iy * nx + ix],in_array[iy * nx + ix]);
+            break 'check_loop;
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o This uses a flat 1D vector for the arrays, as in crsmain1d.rs, since
+     a Cow<[f32]> needs a single contiguous slice. A Cow of a vector of
+     vectors would be possible, but would copy all the rows even if only
+     one of them needed changing, which rather misses the point.
+
+*/
@@ -0,0 +1,193 @@
+//
+//                   c r s m a i n _ r e f c e l l . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, for rows held as Rc<RefCell<Vec<f32>>>.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version measures what run time borrow checking costs. It times the
+//    normal csub() from crssub.rs, working on Vec<Vec<f32>> arrays passed as
+//    &Vec and &mut Vec, against the two routines in crssub_refcell.rs, which
+//    work on arrays whose rows are Rc<RefCell<Vec<f32>>> and borrow them through
+//    the RefCell, either for every element or once for each row. All three
+//    are first checked to give exactly the same output, and then each is timed
+//    for the same number of calls, in two rounds with the order reversed, and
+//    the times are reported relative to the plain version.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_refcell.rs
+//
+// Invocation:
+//    ./crsmain_refcell irpt ny nx
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::cell::RefCell;
+use std::env;
+use std::process;
+use std::rc::Rc;
+use std::time::Instant;
+
+mod crssub;
+mod crserror;
+mod crssub_refcell;
+
+use crssub_refcell::SharedRow;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   //  The plain arrays, and copies of them with each row shared through an
+   //  Rc<RefCell>. Each version writes to its own output array.
+
+   let mut in_array = vec![vec![0.0f32; nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+   let mut out_array = vec![vec![-1.0f32; nx]; ny];
+   let shared_in = shared_array(&in_array);
+   let element_out = shared_array(&out_array);
+   let row_out = shared_array(&out_array);
+
+   //  Check both RefCell versions give exactly the same result as the
+   //  plain one.
+
+   crssub::csub (&in_array,nx,ny,&mut out_array);
+   crssub_refcell::csub (&shared_in,nx,ny,&element_out);
+   crssub_refcell::csub_row (&shared_in,nx,ny,&row_out);
+   let element_ok = check_output("csub()",&out_array,&element_out);
+   let row_ok = check_output("csub_row()",&out_array,&row_out);
+   if !element_ok || !row_ok {
+      process::exit(1);
+   }
+
+   //  Time half the calls to each in one order, and half in the reverse
+   //  order, so none always goes first.
+
+   let mut secs = [0.0f64; 3];
+   for round in 0..2 {
+      let nrpt_round = if round == 0 { nrpt / 2 } else { nrpt - nrpt / 2 };
+      for step in 0..3 {
+         let version = if round == 0 { step } else { 2 - step };
+         let start = Instant::now();
+         match version {
+            0 => for _irpt in 0..nrpt_round {
+               crssub::csub (&in_array,nx,ny,&mut out_array);
+            },
+            1 => for _irpt in 0..nrpt_round {
+               crssub_refcell::csub (&shared_in,nx,ny,&element_out);
+            },
+            _ => for _irpt in 0..nrpt_round {
+               crssub_refcell::csub_row (&shared_in,nx,ny,&row_out);
+            },
+         }
+         secs[version] += start.elapsed().as_secs_f64();
+      }
+   }
+   println!("&mut Vec {:.6} sec, borrow per element {:.6} sec (ratio {:.3}), \
+             borrow per row {:.6} sec (ratio {:.3})",secs[0],secs[1],secs[1] / secs[0],
+                                                       secs[2],secs[2] / secs[0]);
+}
+
+//  shared_array() returns a copy of an array with each row in its own
+//  Rc<RefCell>.
+
+fn shared_array (array: &[Vec<f32>]) -> Vec<SharedRow> {
+   array.iter().map(|row| Rc::new(RefCell::new(row.clone()))).collect()
+}
+
+//  check_output() checks that every element of an array of shared rows is
+//  exactly the same as in the output of the plain version, reporting the
+//  first that isn't, and returns true if they all are.
+
+fn check_output (name: &str,expected: &[Vec<f32>],out_array: &[SharedRow]) -> bool {
+   for (iy,(expected_row,row)) in expected.iter().zip(out_array).enumerate() {
+      let row = row.borrow();
+      for (ix,(&expected_value,&value)) in expected_row.iter().zip(row.iter()).enumerate() {
+         if value != expected_value {
+            println! ("Error: {} gives {} for element [{}][{}], expected {}",
+                                                 name,value,iy,ix,expected_value);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The results, and what the assembler shows, are in the notes at the end
+     of crssub_refcell.rs.
+
+   o The input rows are shared between the two RefCell versions, but each
+     has its own output rows. Passing the same rows as both the input and
+     the output would compile, unlike with &Vec and &mut Vec, but csub()
+     would then panic the first time it tried to borrow a row mutably while
+     it was borrowed to read it.
+
+*/
@@ -0,0 +1,168 @@
+//
+//                   c r s m a i n _ r o w l o o p . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, comparing iterator and indexed row loops.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version times the two routines in crssub_rowloop.rs, which are the
+//    same apart from the inner loop: csub_iter() reads each input row using
+//    row.iter(), and csub_index() using row[ix] for ix in 0..row.len(). Both
+//    are first checked by the same check_output() routine, and then each is
+//    timed for the same number of calls, in two rounds with the order swapped,
+//    and the total times are reported with their ratio.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_rowloop.rs
+//
+// Invocation:
+//    ./crsmain_rowloop irpt ny nx
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+mod crssub_rowloop;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   let mut in_array = vec![vec![0.0f32; nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+
+   //  Each version writes to its own output array, which starts off with
+   //  values that are never the right answer, and both are checked in the
+   //  same way.
+
+   let mut iter_out = vec![vec![-1.0f32; nx]; ny];
+   let mut index_out = vec![vec![-1.0f32; nx]; ny];
+   crssub_rowloop::csub_iter (&in_array,nx,ny,&mut iter_out);
+   crssub_rowloop::csub_index (&in_array,nx,ny,&mut index_out);
+   let iter_ok = check_output("csub_iter()",&in_array,nx,ny,&iter_out);
+   let index_ok = check_output("csub_index()",&in_array,nx,ny,&index_out);
+   if !iter_ok || !index_ok {
+      process::exit(1);
+   }
+
+   //  Time half the calls to each in one order, and half in the other, so
+   //  neither always goes first.
+
+   let (mut iter_secs,mut index_secs) = (0.0,0.0);
+   for round in 0..2 {
+      let nrpt_round = if round == 0 { nrpt / 2 } else { nrpt - nrpt / 2 };
+      for version in 0..2 {
+         let start = Instant::now();
+         if (round + version) % 2 == 0 {
+            for _irpt in 0..nrpt_round {
+               crssub_rowloop::csub_iter (&in_array,nx,ny,&mut iter_out);
+            }
+            iter_secs += start.elapsed().as_secs_f64();
+         } else {
+            for _irpt in 0..nrpt_round {
+               crssub_rowloop::csub_index (&in_array,nx,ny,&mut index_out);
+            }
+            index_secs += start.elapsed().as_secs_f64();
+         }
+      }
+   }
+   println!("row.iter() {:.6} sec, row[ix] {:.6} sec, ratio {:.3}",
+                                  iter_secs,index_secs,iter_secs / index_secs);
+}
+
+//  check_output() checks that every element of an output array has the
+//  value expected, reporting the first that doesn't, and returns true if
+//  they all do.
+
+fn check_output (name: &str,in_array: &[Vec<f32>],nx: usize,ny: usize,
+                                                out_array: &[Vec<f32>]) -> bool {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let expected = in_array[iy][ix] + (ix + iy) as f32;
+         if out_array[iy][ix] != expected {
+            println! ("Error: {} gives {} for element [{}][{}], expected {}",
+                                            name,out_array[iy][ix],iy,ix,expected);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The results, and what the assembler shows, are in the notes at the end
+     of crssub_rowloop.rs.
+
+   o Each version gets its own output array, so neither finds the other's
+     results already in the cache, other than the input array they share.
+
+*/
@@ -0,0 +1,289 @@
+//
+//                    c r s m a i n _ s t r e a m . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, processing a file in blocks.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    The other test programs generate their own arrays in memory. Real
+//    astronomical images can be larger than the available memory, and this
+//    version reads its input array from a file a block of rows at a time,
+//    processes each block using the flat 1D array code in crssub1d.rs, and
+//    writes the result to an output file, so it never holds more than one
+//    block of the array in memory. This extends the study to the interaction
+//    between the file I/O and the array processing.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_stream.rs
+//
+//    This picks up the file handling code from crsfile.rs, the array
+//    processing code from crssub1d.rs and the support for --mmap from
+//    crsos.rs.
+//
+// Invocation:
+//    ./crsmain_stream [options] infile outfile nx ny
+//
+//    where:
+//      infile  is a raw binary file of little-endian f32 values, as written
+//              by the --output option of crsmain.rs.
+//      outfile is the file to be written, in the same format.
+//      nx      is the number of columns in the array.
+//      ny      is the number of rows in the array.
+//
+//    and the options are:
+//      --block-rows=N  sets the number of rows read and processed at a time.
+//                  Default 64.
+//      --verify    once the output file has been written, reads the whole
+//                  of the input file into memory, processes it in one go,
+//                  and checks the result against the output file. This is
+//                  only sensible for small arrays.
+//      --mmap      instead of reading and writing the files, maps both of
+//                  them into memory and processes the whole array in one
+//                  call, directly on the mapped memory, leaving it to the
+//                  operating system to read and write the pages as they
+//                  are used. The output file is first set to the size of
+//                  the array. --block-rows is ignored. Only available on
+//                  Linux, and only on a little-endian machine.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added the --mmap option.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::process;
+use std::time::Instant;
+
+mod crssub1d;
+mod crserror;
+mod crsfile;
+mod crsos;
+
+use crsfile::Endian;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Separate out the options, then check there are the right number of
+   //  positional arguments. Unlike the other test programs, there are no
+   //  sensible defaults for the file names, so they all have to be given.
+
+   let mut block_rows = 64;
+   let mut verify = false;
+   let mut mapped = false;
+   let mut args: Vec<String> = Vec::new();
+   for arg in env::args() {
+      if arg.starts_with("--") {
+         let (name,value) = match arg.find('=') {
+            Some(index) => (&arg[..index],&arg[index + 1..]),
+            None => (&arg[..],""),
+         };
+         match name {
+            "--block-rows" => match value.parse::<usize>() {
+               Ok(number) if number > 0 => block_rows = number,
+               _ => println!("Block rows invalid, using {}",block_rows),
+            },
+            "--verify" => verify = true,
+            "--mmap" => mapped = true,
+            _ => println!("Unknown option {} ignored",arg),
+         }
+      } else {
+         args.push(arg);
+      }
+   }
+   if args.len() != 5 {
+      println!("Usage: {} [--block-rows=N] [--verify] [--mmap] infile outfile nx ny",
+                                                                        args[0]);
+      process::exit(2);
+   }
+   let (nx,ny) = match (args[3].parse::<usize>(),args[4].parse::<usize>()) {
+      (Ok(nx),Ok(ny)) if nx > 0 && ny > 0 => (nx,ny),
+      _ => {
+         println!("Invalid array dimensions {} {}",args[3],args[4]);
+         process::exit(2);
+      }
+   };
+   if mapped {
+      println!("Array has {} rows of {} columns, processed mapped into memory",ny,nx);
+   } else {
+      println!("Array has {} rows of {} columns, processed {} rows at a time",
+                                                            ny,nx,block_rows);
+   }
+
+   let start = Instant::now();
+   let result = if mapped {
+      process_mapped(&args[1],&args[2],nx,ny)
+   } else {
+      process_file(&args[1],&args[2],nx,ny,block_rows)
+   };
+   if let Err(error) = result {
+      println!("Error processing {}: {}",args[1],error);
+      process::exit(1);
+   }
+   let elapsed = start.elapsed().as_secs_f64();
+   println!("Processed in {:.3} seconds, {:.2} MB/s of input",elapsed,
+                        (nx * ny * 4) as f64 / (1024.0 * 1024.0) / elapsed);
+
+   if verify {
+      verify_output(&args[1],&args[2],nx,ny);
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          P r o c e s s  F i l e
+//
+//   Reads the input file a block of rows at a time, processes each block
+//   with csub1d_block(), and writes it to the output file. The last block
+//   may have fewer rows than the others. It is an error if the input file is
+//   too small for the dimensions given.
+
+fn process_file (in_path: &str,out_path: &str,nx: usize,ny: usize,
+                                          block_rows: usize) -> io::Result<()> {
+   let file_size = fs::metadata(in_path)?.len() as usize;
+   if file_size < nx * ny * 4 {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+         format!("file has {} bytes, but {} rows of {} f32 values need {}",
+                                               file_size,ny,nx,nx * ny * 4)));
+   }
+   let mut reader = io::BufReader::new(fs::File::open(in_path)?);
+   let mut writer = io::BufWriter::new(fs::File::create(out_path)?);
+   let mut in_block = vec![0.0f32; nx * block_rows];
+   let mut out_block = vec![0.0f32; nx * block_rows];
+   let mut first_row = 0;
+   while first_row < ny {
+      let rows = block_rows.min(ny - first_row);
+      let values = rows * nx;
+      crsfile::read_f32_values(&mut reader,&mut in_block[..values],Endian::Little)?;
+      crssub1d::csub1d_block(&in_block[..values],nx,rows,first_row,
+                                                     &mut out_block[..values]);
+      crsfile::write_f32_values(&mut writer,&out_block[..values],Endian::Little)?;
+      first_row += rows;
+   }
+   writer.flush()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        P r o c e s s  M a p p e d
+//
+//   Implements --mmap. Maps the start of the input file and the whole of the
+//   output file into memory and runs csub1d_block() over the whole array in
+//   one go, as if it were in memory. The output file is created, or cut
+//   back, and set to the size of the array first (File::set_len() uses
+//   ftruncate()), since a file can't be extended by writing to a mapping of
+//   it. The mapped values are in the machine's byte order, whereas the files
+//   are little-endian, so this only works on a little-endian machine.
+
+fn process_mapped (in_path: &str,out_path: &str,nx: usize,ny: usize) -> io::Result<()> {
+   if cfg!(target_endian = "big") {
+      return Err(io::Error::new(io::ErrorKind::Unsupported,
+                                   "--mmap needs a little-endian machine"));
+   }
+   let in_file = fs::File::open(in_path)?;
+   let file_size = in_file.metadata()?.len() as usize;
+   if file_size < nx * ny * 4 {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+         format!("file has {} bytes, but {} rows of {} f32 values need {}",
+                                               file_size,ny,nx,nx * ny * 4)));
+   }
+   let out_file = OpenOptions::new().read(true).write(true).create(true)
+                                               .truncate(true).open(out_path)?;
+   out_file.set_len((nx * ny * 4) as u64)?;
+   let input = crsos::MappedFile::map(&in_file,nx * ny,false)?;
+   let mut output = crsos::MappedFile::map(&out_file,nx * ny,true)?;
+   crssub1d::csub1d_block(input.values(),nx,ny,0,output.values_mut());
+   output.sync()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         V e r i f y  O u t p u t
+//
+//   Reads the whole of the input file into memory, processes it in one go
+//   using csub1d(), and checks the result matches the output file written
+//   block by block.
+
+fn verify_output (in_path: &str,out_path: &str,nx: usize,ny: usize) {
+   let (in_array,streamed) =
+           match (crsfile::read_f32_file(in_path,Endian::Little),
+                             crsfile::read_f32_file(out_path,Endian::Little)) {
+      (Ok(in_array),Ok(streamed)) => (in_array,streamed),
+      _ => {
+         println!("Unable to read files for verification");
+         return;
+      }
+   };
+   let in_array = in_array[..nx * ny].to_vec();
+   let mut out_array = vec![0.0f32; nx * ny];
+   crssub1d::csub1d(&in_array,nx,ny,&mut out_array);
+   if streamed == out_array {
+      println!("Output matches the in-memory result");
+   } else {
+      println!("Error: output does not match the in-memory result");
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The block is read and written using the buffered reader and writer,
+     so the choice of block size mostly affects how much memory is used and
+     how good use the processing makes of the cache, rather than the size of
+     the actual reads and writes.
+
+   o Any data in the input file past the end of the array is ignored.
+
+   o With --verify, the output written through the mapping is read back in
+     the ordinary way and checked against the in-memory result, and for
+     small arrays (tried with 100 by 77, and 1000 by 1000) it matches.
+
+   o Mapping the files is not the win it might be expected to be, at least
+     once the input file is in the page cache. For an 8000 by 8000 array
+     (256 MB), on a test machine with 6 GB of memory, --mmap took between
+     0.18 and 0.29 seconds, usually about 0.28, against 0.20 to 0.27,
+     usually about 0.24, for the default block by block processing. The
+     copying saved by not reading and writing is made up for by a page
+     fault for every 4 KB page of both files, and by the kernel having to
+     find the dirty pages of the output file when msync() is called. Where
+     mapping should do better is when only part of a large image is
+     actually used, which isn't the case here.
+
+*/
@@ -0,0 +1,253 @@
+//
+//                      c r s m a i n _ s t r i d e d
+//
+// Summary:
+//    2D array access test main routine in Rust, timing strided access to the rows.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version maps how the time taken depends on the access stride. It
+//    uses the routine in crssub_strided.rs, which only sets every stride'th
+//    element of each row, and times the same number of calls for each of a
+//    range of strides from 1 upwards, after checking for each stride that the
+//    elements it should set are right and that the rest are left alone. The
+//    normal csub() from crssub.rs is timed as well, for comparison with a
+//    stride of 1. For each stride it reports the time per element touched, the
+//    time per element of the whole array, and the rates at which useful data
+//    and whole cache lines were moved, which is the usual stride against
+//    bandwidth curve.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_strided.rs
+//
+// Invocation:
+//    ./crsmain_strided irpt ny nx
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+mod crssub;
+mod crserror;
+mod crssub_strided;
+
+//  The strides timed, in elements. Those bigger than the row length, which
+//  would only touch the first element of each row, are skipped.
+
+const STRIDES: [usize; 15] = [1,2,3,4,6,8,12,16,24,32,64,128,256,512,1024];
+
+//  The size of a cache line in f32 elements, used for the cache line rate.
+//  This is 64 bytes, as on most current processors.
+
+const LINE_ELEMENTS: usize = 16;
+
+//  The value the output array is set to before each check. The input values
+//  are all positive, so this can't be a result.
+
+const UNTOUCHED: f32 = -1.0;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the array dimensions and repeat count either from the default
+   //  values or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let mut nx = 2000;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+         if args.len() > 3 {
+            match args[3].parse::<usize>() {
+               Ok(number) => nx = number,
+               Err(_error) => println!("Columns invalid, using {}",nx),
+            };
+         }
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,nx,nrpt);
+
+   let mut in_array = vec![vec![0.0f32; nx]; ny];
+   for iy in 0..ny {
+      for ix in 0..nx {
+         in_array[iy][ix] = (nx - ix + ny - iy) as f32;
+      }
+   }
+   let mut out_array = vec![vec![UNTOUCHED; nx]; ny];
+
+   let start = Instant::now();
+   for _irpt in 0..nrpt {
+      crssub::csub (&in_array,nx,ny,&mut out_array);
+   }
+   let csub_secs = start.elapsed().as_secs_f64();
+   let elements = (nx * ny) as f64 * nrpt as f64;
+   println!("csub() {:.6} sec, {:.4} ns/element",csub_secs,csub_secs * 1.0e9 / elements);
+
+   println!();
+   println!("Stride   ns/touched  ns/element  useful GB/s  lines GB/s");
+   for &stride in STRIDES.iter().filter(|&&stride| stride <= nx.max(1)) {
+
+      //  Check this stride first, starting from an output array that has
+      //  had nothing set, then time it.
+
+      for row in out_array.iter_mut() {
+         row.fill(UNTOUCHED);
+      }
+      crssub_strided::csub (&in_array,nx,ny,&mut out_array,stride);
+      if !check_output(&in_array,&out_array,stride) {
+         process::exit(1);
+      }
+      let start = Instant::now();
+      for _irpt in 0..nrpt {
+         crssub_strided::csub (&in_array,nx,ny,&mut out_array,stride);
+      }
+      let secs = start.elapsed().as_secs_f64();
+
+      //  Each element touched is read and written, so 8 bytes are used, but
+      //  the memory system moves whole cache lines, in and back out again.
+
+      let touched = (crssub_strided::touched(nx,stride) * ny) as f64 * nrpt as f64;
+      let lines = (cache_lines(nx,stride) * ny) as f64 * nrpt as f64;
+      let line_bytes = (LINE_ELEMENTS * std::mem::size_of::<f32>()) as f64;
+      println!("{:6}  {:11.4}  {:10.4}  {:11.3}  {:10.3}",stride,secs * 1.0e9 / touched,
+                   secs * 1.0e9 / elements,touched * 8.0 / secs * 1.0e-9,
+                                           lines * line_bytes * 2.0 / secs * 1.0e-9);
+   }
+}
+
+//  cache_lines() returns the number of cache lines in a row of nx elements
+//  that have at least one element touched for a given stride, assuming the
+//  row starts at the start of a cache line.
+
+fn cache_lines (nx: usize,stride: usize) -> usize {
+   let mut lines = 0;
+   let mut last_line = None;
+   for ix in (0..nx).step_by(stride) {
+      let line = ix / LINE_ELEMENTS;
+      if last_line != Some(line) {
+         lines += 1;
+         last_line = Some(line);
+      }
+   }
+   lines
+}
+
+//  check_output() checks that every element crssub_strided::csub() should
+//  have set for a given stride has the right value, and that every other
+//  element still has the UNTOUCHED value, reporting the first that doesn't,
+//  and returns true if they all do.
+
+fn check_output (in_array: &[Vec<f32>],out_array: &[Vec<f32>],stride: usize) -> bool {
+   for (iy,(in_row,out_row)) in in_array.iter().zip(out_array).enumerate() {
+      for (ix,(&value,&out_value)) in in_row.iter().zip(out_row).enumerate() {
+         let expected = if ix % stride == 0 { value + (ix + iy) as f32 } else { UNTOUCHED };
+         if out_value != expected {
+            println! ("Error: stride {} gives {} for element [{}][{}], expected {}",
+                                                   stride,out_value,iy,ix,expected);
+            return false;
+         }
+      }
+   }
+   true
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The kernel in crssub_strided.rs uses the same indexing as crssub.rs,
+     with bounds checks, and this matters for what the curve shows. For the
+     default 10 rows of 2000 columns everything is in the cache, and the time
+     per element touched stayed between about 1.3 and 2.3 ns for every
+     stride: it's the instructions for each element, not the memory, that
+     set the time, and the time per element of the whole array just falls
+     in proportion to the stride. csub() itself took about 1.25 ns per
+     element, and the strided version with a stride of 1 about 1.5 ns.
+
+   o To see the memory system the arrays have to be well out of the cache.
+     With 8000 rows of 8000 columns (256 MBytes for each array, on a machine
+     with a 105 MByte L3 cache) and 3 repeats:
+
+     Stride   ns/touched  ns/element  useful GB/s  lines GB/s
+          1       1.0915      1.0915        7.329       7.329
+          4       2.4188      0.6047        3.307      13.230
+          8       4.3609      0.5451        1.834      14.676
+         16       9.1278      0.5705        0.876      14.023
+         24      13.5223      0.5646        0.592       9.466
+         32      20.8565      0.6518        0.384       6.137
+         64      35.1744      0.5496        0.227       3.639
+        128      36.4241      0.2868        0.220       3.514
+        256      33.2681      0.1331        0.240       3.848
+       1024      25.3142      0.0253        0.316       5.056
+
+     A stride of 1 is still limited by the instructions. From a stride of 3
+     or 4 up to 16 the time per element of the whole array stays at about
+     0.55 to 0.6 ns - the time to bring in and write back each cache line,
+     whether one element of it is used or all sixteen - and the rate for
+     whole cache lines sits at about 14 GBytes/sec. At 24 and 32 the whole
+     array time hardly changes even though only every second cache line, or
+     fewer, is touched, presumably because the prefetchers are still bringing
+     in pairs of lines, so the line rate as worked out here (only counting
+     the lines touched) falls. By 64, 256 bytes between elements, each
+     element touched costs about 35 ns, which looks like most of a trip to
+     memory, and the useful rate is down to a thirtieth of the dense one.
+     Past that the time per element touched improves slowly, to about 25 ns
+     at 1024, where each element is on its own 4 KByte page.
+
+   o With the smaller 4000 by 4000 arrays, the strides of 128 and above got
+     faster again, to about 15 ns per element touched, not because the
+     access got any better but because the lines touched (8 MBytes at a
+     stride of 128) fit in the L3 cache and stay there from one call to the
+     next. The curve only means something while the lines touched are still
+     bigger than the cache, which is why the large arrays are needed.
+
+   o check_output() looks at every element, not just the ones touched, so
+     it also checks that the elements between them are left alone; the
+     output array is reset before each stride is checked so one stride can't
+     hide a mistake in another.
+
+*/
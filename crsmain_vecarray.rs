@@ -0,0 +1,133 @@
+//
+//                  c r s m a i n _ v e c a r r a y . r s
+//
+// Summary:
+//    2D array access test main routine in Rust, using a vector of fixed-size arrays.
+//
+// Introduction:
+//    This is a test program written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays - the sort of
+//    thing that are common in astronomy and similar scientific disciplines.
+//    The problem chosen is a trivial one: given an 2D array, add to each
+//    element the sum of its two indices and return the result in a second,
+//    similarly-sized array.
+//
+// This version:
+//    This version uses the csub() routine in crssub_vecarray.rs, which holds
+//    the arrays as vectors of fixed-size arrays, Vec<[f32; N]>, with the number
+//    of columns N fixed at compile time. Here N is set to 2000, the default
+//    number of columns used by crsmain.rs, so the two programs can be compared
+//    directly with their default settings.
+//
+// Building:
+//    rustc -O -C target-cpu=native -C opt-level=3 crsmain_vecarray.rs
+//
+// Invocation:
+//    ./crsmain_vecarray irpt ny
+//
+//    where:
+//      irpt  is the number of times the subroutine is called - default 100000.
+//      ny    is the number of rows in the array tested - default 10.
+//
+//    The number of columns is fixed at 2000, the value of NX. To test a
+//    different number, change NX and recompile.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+
+mod crssub_vecarray;
+
+//  The number of columns in the arrays, fixed at compile time.
+
+const NX: usize = 2000;
+
+//  ----------------------------------------------------------------------------
+//
+//                             M a i n  P r o g r a m
+
+fn main() {
+
+   //  Set the number of rows and repeat count either from the default values
+   //  or values supplied on the command line, as in crsmain.rs.
+
+   let mut nrpt = 100000;
+   let mut ny = 10;
+   let args: Vec<String> = env::args().collect();
+   if args.len() > 1 {
+      match args[1].parse::<usize>() {
+         Ok(number) => nrpt = number,
+         Err(_error) => println!("Repeats invalid, using {}",nrpt),
+      };
+      if args.len() > 2 {
+         match args[2].parse::<usize>() {
+            Ok(number) => ny = number,
+            Err(_error) => println!("Rows invalid, using {}",ny),
+         };
+      }
+   }
+   println!("Arrays have {} rows of {} columns, repeats = {}",ny,NX,nrpt);
+
+   //  Set up the input and output arrays, and set the input values, using
+   //  the usual descending values.
+
+   let mut in_array = vec![[0.0f32; NX]; ny];
+   let mut out_array = vec![[0.0f32; NX]; ny];
+   for iy in 0..ny {
+      for ix in 0..NX {
+         in_array[iy][ix] = (NX - ix + ny - iy) as f32;
+      }
+   }
+
+   //  Repeat the call to the manipulating subroutine.
+
+   for _irpt in 1..=nrpt {
+      crssub_vecarray::csub (&in_array,&mut out_array,ny);
+   }
+
+   //  Check that we got the expected results.
+
+   'check_loop :
+   for iy in 0..ny {
+      for ix in 0..NX {
+         if out_array[iy][ix] != (in_array[iy][ix] + (ix + iy) as f32) {
+            println! ("Error {} {} {} {}",
+                           ix,iy,out_array[iy][ix],in_array[iy][ix]);
+            break 'check_loop;
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o vec![[0.0f32; NX]; ny] builds the whole array as a single allocation,
+     since a [f32; NX] is a plain value that can be copied, not a separate
+     heap allocation like a Vec<f32>.
+
+*/
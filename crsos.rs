@@ -0,0 +1,377 @@
+//
+//                             c r s o s . r s
+//
+// Summary:
+//    Operating system specific support for the 2D array access tests in Rust.
+//
+// Introduction:
+//    Some of the test modes need things from the operating system that Rust's
+//    standard library doesn't provide, such as control over which processor a
+//    thread runs on. The programs are built without any external crates, so
+//    the libc crate isn't available, but the C library itself is always linked
+//    into a Rust program on Linux, and its functions can be declared and called
+//    directly. This module keeps all such code in one place, with versions for
+//    other systems that simply report the facility as unavailable.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added page_faults().
+//    14th Oct 2026. Added cache_sizes().
+//    14th Oct 2026. Added smt_siblings().
+//    14th Oct 2026. Added MappedFile.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This module is shared by a number of programs, not all of which use
+//  every routine in it.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::io;
+#[cfg(target_os = "linux")]
+use std::os::raw::{c_long,c_void};
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+
+//  The number of 64-bit words in a Linux cpu_set_t, which has room for 1024
+//  processors.
+
+const CPU_SET_WORDS: usize = 16;
+
+//  A CpuMask is a set of processors a thread is allowed to run on.
+
+#[derive(Clone)]
+pub struct CpuMask {
+   words: [u64; CPU_SET_WORDS],
+}
+
+impl CpuMask {
+
+   //  Returns a mask that allows only the one specified processor.
+
+   pub fn single (cpu: usize) -> CpuMask {
+      let mut words = [0u64; CPU_SET_WORDS];
+      if cpu < CPU_SET_WORDS * 64 {
+         words[cpu / 64] = 1u64 << (cpu % 64);
+      }
+      CpuMask { words }
+   }
+
+   //  Returns the numbers of the processors in the mask, in ascending order.
+
+   pub fn cpus (&self) -> Vec<usize> {
+      (0..CPU_SET_WORDS * 64)
+           .filter(|cpu| self.words[cpu / 64] & (1u64 << (cpu % 64)) != 0).collect()
+   }
+}
+
+#[cfg(target_os = "linux")]
+extern "C" {
+   fn sched_getaffinity (pid: i32,cpusetsize: usize,mask: *mut u64) -> i32;
+   fn sched_setaffinity (pid: i32,cpusetsize: usize,mask: *const u64) -> i32;
+}
+
+//  Returns the set of processors the calling thread may run on, or None if
+//  this isn't available.
+
+#[cfg(target_os = "linux")]
+pub fn get_affinity () -> Option<CpuMask> {
+   let mut mask = CpuMask { words: [0u64; CPU_SET_WORDS] };
+   let size = std::mem::size_of_val(&mask.words);
+   let status = unsafe { sched_getaffinity(0,size,mask.words.as_mut_ptr()) };
+   if status == 0 { Some(mask) } else { None }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_affinity () -> Option<CpuMask> {
+   None
+}
+
+//  Restricts the calling thread to the processors in a mask, returning
+//  false if this can't be done.
+
+#[cfg(target_os = "linux")]
+pub fn set_affinity (mask: &CpuMask) -> bool {
+   let size = std::mem::size_of_val(&mask.words);
+   unsafe { sched_setaffinity(0,size,mask.words.as_ptr()) == 0 }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity (_mask: &CpuMask) -> bool {
+   false
+}
+
+//  The rusage structure filled in by getrusage(). Only the page fault
+//  counts are used, but the layout has to match the C structure. Each
+//  timeval is two C longs.
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct Rusage {
+   ru_utime: [c_long; 2],
+   ru_stime: [c_long; 2],
+   ru_maxrss: c_long,
+   ru_ixrss: c_long,
+   ru_idrss: c_long,
+   ru_isrss: c_long,
+   ru_minflt: c_long,
+   ru_majflt: c_long,
+   ru_other: [c_long; 8],
+}
+
+#[cfg(target_os = "linux")]
+extern "C" {
+   fn getrusage (who: i32,usage: *mut Rusage) -> i32;
+}
+
+//  Returns the numbers of minor and major page faults the process has had
+//  so far, or None if this isn't available. A minor fault is one that was
+//  dealt with without reading from disk, such as the first access to a
+//  newly allocated page of memory.
+
+#[cfg(target_os = "linux")]
+pub fn page_faults () -> Option<(u64,u64)> {
+   const RUSAGE_SELF: i32 = 0;
+   let mut usage = Rusage { ru_utime: [0; 2],ru_stime: [0; 2],ru_maxrss: 0,
+      ru_ixrss: 0,ru_idrss: 0,ru_isrss: 0,ru_minflt: 0,ru_majflt: 0,ru_other: [0; 8] };
+   if unsafe { getrusage(RUSAGE_SELF,&mut usage) } == 0 {
+      Some((usage.ru_minflt as u64,usage.ru_majflt as u64))
+   } else {
+      None
+   }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn page_faults () -> Option<(u64,u64)> {
+   None
+}
+
+//  Returns the level and size in bytes of each of the data caches used by
+//  the first processor, in order of level, as listed by Linux under /sys.
+//  Instruction caches are left out. If the information isn't available, as
+//  on other systems, an empty vector is returned.
+
+pub fn cache_sizes () -> Vec<(u32,usize)> {
+   let mut caches = Vec::new();
+   for index in 0.. {
+      let directory = format!("/sys/devices/system/cpu/cpu0/cache/index{}",index);
+      let read = |name: &str| fs::read_to_string(format!("{}/{}",directory,name))
+                                           .map(|text| text.trim().to_string());
+      let (level,kind,size) = match (read("level"),read("type"),read("size")) {
+         (Ok(level),Ok(kind),Ok(size)) => (level,kind,size),
+         _ => break,
+      };
+      if kind == "Instruction" {
+         continue;
+      }
+      if let (Ok(level),Some(bytes)) = (level.parse::<u32>(),parse_size(&size)) {
+         caches.push((level,bytes));
+      }
+   }
+   caches.sort();
+   caches
+}
+
+//  Returns the other processors that share a core with the specified one
+//  using simultaneous multithreading (hyperthreading), as listed by Linux
+//  under /sys. If there are none, or the information isn't available, as
+//  on other systems, an empty vector is returned.
+
+pub fn smt_siblings (cpu: usize) -> Vec<usize> {
+   let path = format!("/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list",cpu);
+   match fs::read_to_string(path) {
+      Ok(text) => parse_cpu_list(text.trim()).into_iter()
+                                         .filter(|&sibling| sibling != cpu).collect(),
+      Err(_) => Vec::new(),
+   }
+}
+
+//  A file mapped into memory with mmap() and treated as an array of f32
+//  values in the machine's own byte order. The mapping is shared, so any
+//  changes made through values_mut() end up in the file - when sync() is
+//  called, or at the latest when the mapping is removed as it is dropped.
+
+pub struct MappedFile {
+   address: *mut f32,
+   count: usize,
+}
+
+#[cfg(target_os = "linux")]
+extern "C" {
+   fn mmap (address: *mut c_void,length: usize,prot: i32,flags: i32,fd: i32,
+                                                    offset: i64) -> *mut c_void;
+   fn munmap (address: *mut c_void,length: usize) -> i32;
+   fn msync (address: *mut c_void,length: usize,flags: i32) -> i32;
+}
+
+#[cfg(target_os = "linux")]
+const PROT_READ: i32 = 1;
+#[cfg(target_os = "linux")]
+const PROT_WRITE: i32 = 2;
+#[cfg(target_os = "linux")]
+const MAP_SHARED: i32 = 1;
+#[cfg(target_os = "linux")]
+const MS_SYNC: i32 = 4;
+
+impl MappedFile {
+
+   //  Maps the first count f32 values of an open file, which must be at
+   //  least that big. If writable is set, the file must have been opened
+   //  for both reading and writing. An empty mapping isn't allowed.
+
+   #[cfg(target_os = "linux")]
+   pub fn map (file: &fs::File,count: usize,writable: bool) -> io::Result<MappedFile> {
+      let length = count * std::mem::size_of::<f32>();
+      if length == 0 {
+         return Err(io::Error::new(io::ErrorKind::InvalidInput,"empty mapping"));
+      }
+      let prot = if writable { PROT_READ | PROT_WRITE } else { PROT_READ };
+      let address = unsafe {
+         mmap(std::ptr::null_mut(),length,prot,MAP_SHARED,file.as_raw_fd(),0)
+      };
+      if address as isize == -1 {
+         return Err(io::Error::last_os_error());
+      }
+      Ok(MappedFile { address: address as *mut f32,count })
+   }
+
+   #[cfg(not(target_os = "linux"))]
+   pub fn map (_file: &fs::File,_count: usize,_writable: bool) -> io::Result<MappedFile> {
+      Err(io::Error::new(io::ErrorKind::Unsupported,"mmap() is not available"))
+   }
+
+   //  The mapped values. The mapping is page aligned, so there is no
+   //  problem with the alignment of the f32 values.
+
+   pub fn values (&self) -> &[f32] {
+      unsafe { std::slice::from_raw_parts(self.address,self.count) }
+   }
+
+   //  The mapped values, for changing. This is only valid for a mapping
+   //  made with writable set; writing to a read-only one is a segmentation
+   //  fault, not an error.
+
+   pub fn values_mut (&mut self) -> &mut [f32] {
+      unsafe { std::slice::from_raw_parts_mut(self.address,self.count) }
+   }
+
+   //  Waits until any changes made to the values have been written to the
+   //  file, using msync().
+
+   #[cfg(target_os = "linux")]
+   pub fn sync (&self) -> io::Result<()> {
+      let length = self.count * std::mem::size_of::<f32>();
+      if unsafe { msync(self.address as *mut c_void,length,MS_SYNC) } == 0 {
+         Ok(())
+      } else {
+         Err(io::Error::last_os_error())
+      }
+   }
+
+   #[cfg(not(target_os = "linux"))]
+   pub fn sync (&self) -> io::Result<()> {
+      Ok(())
+   }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for MappedFile {
+   fn drop (&mut self) {
+      let length = self.count * std::mem::size_of::<f32>();
+      unsafe { munmap(self.address as *mut c_void,length) };
+   }
+}
+
+//  Converts a list of processors as given under /sys, such as "0,4" or
+//  "0-3,8", to the processor numbers. Anything that can't be parsed is left
+//  out.
+
+fn parse_cpu_list (text: &str) -> Vec<usize> {
+   let mut cpus = Vec::new();
+   for item in text.split(',') {
+      match item.split_once('-') {
+         Some((first,last)) => {
+            if let (Ok(first),Ok(last)) = (first.parse::<usize>(),last.parse::<usize>()) {
+               cpus.extend(first..=last);
+            }
+         }
+         None => {
+            if let Ok(cpu) = item.parse::<usize>() {
+               cpus.push(cpu);
+            }
+         }
+      }
+   }
+   cpus
+}
+
+//  Converts a size as given under /sys, such as "48K" or "32M", to bytes.
+
+fn parse_size (text: &str) -> Option<usize> {
+   let (digits,multiplier) = match text.chars().last() {
+      Some('K') => (&text[..text.len() - 1],1024),
+      Some('M') => (&text[..text.len() - 1],1024 * 1024),
+      Some('G') => (&text[..text.len() - 1],1024 * 1024 * 1024),
+      _ => (text,1),
+   };
+   digits.parse::<usize>().ok().map(|number| number * multiplier)
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A pid of zero passed to sched_getaffinity() or sched_setaffinity()
+     means the calling thread, not the whole process, so the other threads
+     of a program aren't affected.
+
+   o The mask is the same size as the cpu_set_t used by glibc, so the
+     calls work on any machine with up to 1024 processors. On a machine
+     with more, sched_getaffinity() returns an error and get_affinity()
+     returns None.
+
+   o The counts from getrusage() are for the whole process, so anything
+     else the program does between two calls - printing, say, or another
+     thread allocating memory - is counted too. For a single call to csub()
+     on a large array, the faults from the array accesses swamp any others.
+
+   o The cache sizes under /sys are those of each cache, not the amount
+     available to one processor. A level 3 cache is normally shared by all
+     the processors of a chip (or a group of them), and a level 2 often by
+     a pair of hyperthreads, so a program running alongside others may find
+     rather less than this available to it.
+
+   o The order of the processors in a thread_siblings_list isn't anything
+     to rely on: on most Intel machines the siblings of processor 0 are
+     listed as "0,N" for N processors per hyperthread, but on others they are
+     "0-1", so smt_siblings() just removes the processor asked about.
+
+   o MappedFile only handles mappings starting at the beginning of a file,
+     which is all crsmain_stream needs, so there is no need to worry about
+     the offset passed to mmap() being a multiple of the page size. The
+     values of the PROT_, MAP_ and MS_ constants are those used by Linux;
+     MS_SYNC, at least, is different on other systems, which is one reason
+     it is only available on Linux, like everything else here.
+
+*/
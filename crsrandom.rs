@@ -0,0 +1,110 @@
+//
+//                         c r s r a n d o m . r s
+//
+// Summary:
+//    Pseudo-random numbers for the 2D array access tests in Rust.
+//
+// Introduction:
+//    Some of the test programs in this study need random numbers, for example to
+//    pick array sizes to test or to fill the input arrays with values the
+//    compiler can't know anything about in advance. Rust's standard library has
+//    no random number generator, and the programs are built without any other
+//    crates, so this module provides a simple one. It uses the SplitMix64
+//    algorithm, which is fast, has a single 64-bit word of state, and gives the
+//    same sequence for the same seed on any machine. That is plenty for these
+//    purposes, but nowhere near good enough for anything statistical.
+//
+// The algorithm:
+//    SplitMix64 is the generator described by Steele, Lea and Flood in "Fast
+//    Splittable Pseudorandom Number Generators" (OOPSLA 2014), in the form
+//    used by Java's SplittableRandom and by Sebastiano Vigna's reference C
+//    code, splitmix64.c. The state is a single u64, set to the seed. To get
+//    each value, the state is first advanced by adding 0x9E3779B97F4A7C15,
+//    and the new state z is then mixed, with all arithmetic modulo 2^64:
+//
+//       z = (z ^ (z >> 30)) * 0xBF58476D1CE4E5B9
+//       z = (z ^ (z >> 27)) * 0x94D049BB133111EB
+//       value = z ^ (z >> 31)
+//
+//    This uses only integer operations whose results are fully defined, so
+//    the same seed gives the same values on every machine and with every
+//    compiler. A seed of 0 gives 0xE220A8397B1DCDAF, 0x6E789E6AA1B965F4 and
+//    0x06C45D188009454F as its first three values, as does the reference
+//    code, which is checked by the example below. An f32 in the range 0 to 1
+//    is made from the top 24 bits of a value, divided by 2^24, which is exact.
+//    This is how crsmain.rs fills its arrays with --seed, so --seed=42 gives
+//    exactly the same input values on any machine.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Documented the algorithm, with a check of known values.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This module is shared by a number of programs, not all of which use
+//  every routine in it.
+
+#![allow(dead_code)]
+
+/// A SplitMix64 generator. Two generators created with the same seed return
+/// the same sequence of values, on any machine.
+///
+/// ```
+/// let mut random = crslib::crsrandom::Random::new(0);
+/// assert_eq!(random.next_u64(),0xE220_A839_7B1D_CDAF);
+/// assert_eq!(random.next_u64(),0x6E78_9E6A_A1B9_65F4);
+/// assert_eq!(random.next_u64(),0x06C4_5D18_8009_454F);
+///
+/// //  The first value for a seed of 42 is 0xBDD732262FEB6E95, and next_f32()
+/// //  uses its top 24 bits.
+///
+/// let mut random = crslib::crsrandom::Random::new(42);
+/// assert_eq!(random.next_f32(),0xBD_D732 as f32 / (1u32 << 24) as f32);
+/// ```
+pub struct Random {
+   state: u64,
+}
+
+impl Random {
+   pub fn new (seed: u64) -> Random {
+      Random { state: seed }
+   }
+
+   //  Returns the next value in the sequence.
+
+   pub fn next_u64 (&mut self) -> u64 {
+      self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+      let mut z = self.state;
+      z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+      z ^ (z >> 31)
+   }
+
+   //  Returns the next value as an f32 in the range 0.0 to 1.0 (but never
+   //  exactly 1.0). This uses the top 24 bits, as that is all an f32 can
+   //  hold exactly.
+
+   pub fn next_f32 (&mut self) -> f32 {
+      (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+   }
+}
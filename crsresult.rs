@@ -0,0 +1,398 @@
+//
+//                         c r s r e s u l t . r s
+//
+// Summary:
+//    Machine-readable test results for the 2D array access tests in Rust.
+//
+// Introduction:
+//    The test programs in this study print their results in a form intended to
+//    be read by a person. When a lot of tests are being run, for example to
+//    compare different compilers or compiler options, it is more useful to have
+//    the results in a form that can be read by another program. This module
+//    provides the routines used to write the results of a test as CSV or JSON.
+//
+// This version:
+//    Each test produces a single record, giving the version of csub() used, the
+//    array dimensions, the number of calls, and the time taken. Records are
+//    appended to the named file, so a series of tests can all write to the same
+//    file. A CSV file gets a header line when it is first created; a JSON file
+//    has one JSON object per line (the format usually called JSON Lines), so
+//    it can be appended to in the same way. Optionally, each record can also
+//    carry all the settings that went into it, so it needs nothing else to
+//    say how it was produced.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Records can carry the settings used for the test.
+//    14th Oct 2026. Added write_gnuplot().
+//    14th Oct 2026. Added read_csv().
+//    14th Oct 2026. Added write_bench_json().
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This module is shared by a number of programs, not all of which use
+//  every routine in it.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::io;
+use std::io::{BufRead,Write};
+use std::process::Command;
+use std::time::{SystemTime,UNIX_EPOCH};
+
+use crate::crstime::Summary;
+
+//  The result of a single test. The time is the total for all the calls,
+//  in nanoseconds.
+
+pub struct Record {
+   pub kernel: String,
+   pub nx: usize,
+   pub ny: usize,
+   pub nrpt: usize,
+   pub narrays: usize,
+   pub nanosecs: f64,
+}
+
+impl Record {
+
+   //  Returns the time per element processed, in nanoseconds.
+
+   pub fn ns_per_element (&self) -> f64 {
+      self.nanosecs / (self.nrpt * self.nx * self.ny).max(1) as f64
+   }
+}
+
+//  The header line written at the top of a CSV file.
+
+pub const CSV_HEADER: &str = "kernel,nx,ny,nrpt,narrays,seconds,ns_per_element";
+
+//  ----------------------------------------------------------------------------
+//
+//                          A p p e n d  C S V
+//
+//   Appends a record to the named CSV file, creating the file and writing
+//   the header line first if the file doesn't exist or is empty. If any
+//   settings are passed, they go in an extra 'settings' column as a single
+//   quoted field of space-separated name=value pairs. A file that already
+//   has a different header line - one with or without that column - is
+//   left alone and an error returned, rather than mixing the two kinds of
+//   line in the one file.
+
+pub fn append_csv (path: &str,record: &Record,settings: &[(&str,String)])
+                                                            -> io::Result<()> {
+   let header = if settings.is_empty() {
+      CSV_HEADER.to_string()
+   } else {
+      format!("{},settings",CSV_HEADER)
+   };
+   let mut first_line = String::new();
+   if let Ok(file) = fs::File::open(path) {
+      io::BufReader::new(file).read_line(&mut first_line)?;
+   }
+   let is_new = first_line.is_empty();
+   if !is_new && first_line.trim_end() != header {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                  "file has a different CSV header line"));
+   }
+   let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+   if is_new {
+      writeln!(file,"{}",header)?;
+   }
+   write!(file,"{},{},{},{},{},{:.9},{:.6}",record.kernel,record.nx,record.ny,
+            record.nrpt,record.narrays,record.nanosecs * 1.0e-9,
+                                                      record.ns_per_element())?;
+   if !settings.is_empty() {
+      let pairs: Vec<String> = settings.iter()
+                         .map(|(name,value)| format!("{}={}",name,value)).collect();
+      write!(file,",\"{}\"",pairs.join(" ").replace('"',"\"\""))?;
+   }
+   writeln!(file)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                            R e a d  C S V
+//
+//   Reads back all the records in a CSV file written by append_csv(), in the
+//   order they appear in the file. The header line has to start with
+//   CSV_HEADER; any settings column is ignored. The time is taken from the
+//   seconds column, which has more significant figures than ns_per_element.
+//   Blank lines are skipped, and any other line that can't be read is an
+//   error, giving its line number.
+
+pub fn read_csv (path: &str) -> io::Result<Vec<Record>> {
+   let text = fs::read_to_string(path)?;
+   let mut lines = text.lines();
+   if !lines.next().is_some_and(|header| header.starts_with(CSV_HEADER)) {
+      return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                   format!("{} is not a CSV results file",path)));
+   }
+   let mut records = Vec::new();
+   for (iline,line) in lines.enumerate() {
+      if line.trim().is_empty() {
+         continue;
+      }
+      let fields: Vec<&str> = line.splitn(8,',').collect();
+      let number = |ifield: usize| fields.get(ifield).and_then(|field| field.parse::<usize>().ok());
+      let seconds = fields.get(5).and_then(|field| field.parse::<f64>().ok());
+      match (number(1),number(2),number(3),number(4),seconds) {
+         (Some(nx),Some(ny),Some(nrpt),Some(narrays),Some(seconds)) => {
+            records.push(Record { kernel: fields[0].to_string(),nx,ny,nrpt,narrays,
+                                                    nanosecs: seconds * 1.0e9 });
+         }
+         _ => {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                             format!("line {} of {} can't be read",iline + 2,path)));
+         }
+      }
+   }
+   Ok(records)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          A p p e n d  J S O N
+//
+//   Appends a record to the named file as a single line holding a JSON
+//   object, creating the file if necessary. Any settings passed are added
+//   as a nested "settings" object, with all the values as strings.
+
+pub fn append_json (path: &str,record: &Record,settings: &[(&str,String)])
+                                                            -> io::Result<()> {
+   let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+   write!(file,"{{\"kernel\": \"{}\", \"nx\": {}, \"ny\": {}, \"nrpt\": {}, \
+                   \"narrays\": {}, \"seconds\": {:.9}, \"ns_per_element\": {:.6}",
+            json_escape(&record.kernel),record.nx,record.ny,record.nrpt,
+            record.narrays,record.nanosecs * 1.0e-9,record.ns_per_element())?;
+   if !settings.is_empty() {
+      let pairs: Vec<String> = settings.iter().map(|(name,value)|
+                   format!("\"{}\": \"{}\"",json_escape(name),json_escape(value))).collect();
+      write!(file,", \"settings\": {{{}}}",pairs.join(", "))?;
+   }
+   writeln!(file,"}}")
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                      W r i t e  B e n c h  J S O N
+//
+//   Writes the statistics for a set of timings to the named file in the
+//   form hyperfine uses for its --export-json files, so they can be read by
+//   the same tools. The file holds a single JSON object with a "results"
+//   array, here with one entry, giving the command and the mean, standard
+//   deviation, median, minimum and maximum, in seconds:
+//
+//      {"results": [{"command": "...", "mean": ..., "stddev": ...,
+//                    "median": ..., "min": ..., "max": ...}]}
+//
+//   The times in the summary are in nanoseconds. hyperfine also gives the
+//   user and system times and the individual times, which are left out.
+//   Unlike append_json(), this replaces any existing file.
+
+pub fn write_bench_json (path: &str,command: &str,summary: &Summary) -> io::Result<()> {
+   let mut file = fs::File::create(path)?;
+   writeln!(file,"{{\"results\": [{{\"command\": \"{}\", \"mean\": {:e}, \
+                   \"stddev\": {:e}, \"median\": {:e}, \"min\": {:e}, \"max\": {:e}}}]}}",
+            json_escape(command),summary.mean * 1.0e-9,summary.stddev * 1.0e-9,
+            summary.median * 1.0e-9,summary.min * 1.0e-9,summary.max * 1.0e-9)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        W r i t e  G n u p l o t
+//
+//   Writes a table of times per element, with a row for each array size and
+//   a column for each version of csub(), to PREFIX.dat, and a gnuplot script
+//   that plots them against the working set size on a log scale to
+//   PREFIX.plt. Running 'gnuplot PREFIX.plt' then writes the chart to
+//   PREFIX.png. Each row is given as (nx,ny,times), with the times in the
+//   same order as the names. The working set counts both the input and the
+//   output array. Files of the same name are overwritten.
+
+pub fn write_gnuplot (prefix: &str,names: &[&str],rows: &[(usize,usize,Vec<f64>)])
+                                                            -> io::Result<()> {
+
+   //  The first line of the data file holds the column names, which gnuplot
+   //  uses for the key, so it isn't a comment line.
+
+   let mut file = fs::File::create(format!("{}.dat",prefix))?;
+   writeln!(file,"columns rows bytes {}",names.join(" "))?;
+   for (nx,ny,ns_per_element) in rows.iter() {
+      write!(file,"{} {} {}",nx,ny,2 * nx * ny * std::mem::size_of::<f32>())?;
+      for value in ns_per_element.iter() {
+         write!(file," {:.6}",value)?;
+      }
+      writeln!(file)?;
+   }
+
+   let mut file = fs::File::create(format!("{}.plt",prefix))?;
+   writeln!(file,"# Plots {}.dat, written by crsmain --matrix. Run as: gnuplot {}.plt",
+                                                                     prefix,prefix)?;
+   writeln!(file,"set terminal png size 900,600")?;
+   writeln!(file,"set output \"{}.png\"",prefix)?;
+   writeln!(file,"set logscale x 2")?;
+   writeln!(file,"set xlabel \"Working set, input plus output (bytes)\"")?;
+   writeln!(file,"set ylabel \"Time per element (ns)\"")?;
+   writeln!(file,"set key left top")?;
+   writeln!(file,"set grid")?;
+   writeln!(file,"plot for [i=4:{}] \"{}.dat\" using 3:i with linespoints title columnheader(i)",
+                                                               names.len() + 3,prefix)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          J S O N  E s c a p e
+//
+//   Returns a string with any characters that can't appear as they are in
+//   a JSON string escaped.
+
+fn json_escape (text: &str) -> String {
+   let mut escaped = String::with_capacity(text.len());
+   for c in text.chars() {
+      match c {
+         '"' => escaped.push_str("\\\""),
+         '\\' => escaped.push_str("\\\\"),
+         c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}",c as u32)),
+         c => escaped.push(c),
+      }
+   }
+   escaped
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          A u t o  N a m e
+//
+//   Returns a file name for a set of results, made up from the name of the
+//   program, the version of rustc used, the optimisation level, the date
+//   and time, and the extension passed (eg "csv"), for example
+//   crsmain_rustc-1.95.0_opt-3_20261014T093012.csv. Results from the same
+//   test built with different compilers or options then go to different
+//   files, as do results from runs made at different times.
+
+pub fn auto_name (program: &str,extension: &str) -> String {
+   format!("{}_{}_{}_{}.{}",program,rustc_version(),opt_level(),
+                                                  timestamp(),extension)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                        R u s t c  V e r s i o n
+//
+//   Returns the rustc version, as used in auto_name(), eg "rustc-1.95.0".
+//   rustc doesn't record its version in the programs it compiles, so this
+//   has to be passed in the CRS_RUSTC_VERSION environment variable when
+//   the program is built, as described in crsmain.rs. If it wasn't, this
+//   falls back on asking whatever rustc is on the path, which will usually
+//   - but not always - be the one used.
+
+pub fn rustc_version () -> String {
+   let version = match option_env!("CRS_RUSTC_VERSION") {
+      Some(version) => version.to_string(),
+      None => match Command::new("rustc").arg("--version").output() {
+         Ok(output) if output.status.success() =>
+                             String::from_utf8_lossy(&output.stdout).to_string(),
+         _ => String::from("rustc unknown"),
+      },
+   };
+
+   //  "rustc 1.95.0 (59807616e 2026-04-14)" becomes "rustc-1.95.0".
+
+   version.split_whitespace().take(2).collect::<Vec<&str>>().join("-")
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                          O p t  L e v e l
+//
+//   Returns the optimisation level, as used in auto_name(), eg "opt-3".
+//   Like the rustc version, this can only be known if it is passed in the
+//   CRS_OPT_LEVEL environment variable when the program is built. If it
+//   wasn't, this can only tell whether debug assertions were enabled,
+//   which they are by default for an unoptimised build.
+
+pub fn opt_level () -> String {
+   match option_env!("CRS_OPT_LEVEL") {
+      Some(level) => format!("opt-{}",level),
+      None if cfg!(debug_assertions) => String::from("debug"),
+      None => String::from("opt"),
+   }
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           T i m e s t a m p
+//
+//   Returns the current date and time (UTC) in the compact ISO 8601 form
+//   YYYYMMDDTHHMMSS.
+
+fn timestamp () -> String {
+   let secs = SystemTime::now().duration_since(UNIX_EPOCH)
+                                            .map(|d| d.as_secs()).unwrap_or(0);
+   let days = (secs / 86400) as i64;
+   let (hours,minutes,seconds) = ((secs / 3600) % 24,(secs / 60) % 60,secs % 60);
+
+   //  This converts a count of days since 1970 to a calendar date, using
+   //  Howard Hinnant's civil_from_days() algorithm.
+
+   let z = days + 719468;
+   let era = z.div_euclid(146097);
+   let doe = z - era * 146097;
+   let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+   let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+   let mp = (5 * doy + 2) / 153;
+   let day = doy - (153 * mp + 2) / 5 + 1;
+   let month = if mp < 10 { mp + 3 } else { mp - 9 };
+   let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+   format!("{:04}{:02}{:02}T{:02}{:02}{:02}",year,month,day,hours,minutes,seconds)
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The JSON is written by hand rather than using a crate like serde_json,
+     since these programs are built using plain rustc with no access to
+     external crates, and the records are simple enough not to need one.
+
+   o The settings written with --machine-readable go into a single CSV
+     column, rather than a column each, so that adding another setting
+     later doesn't change the header line and make new results impossible
+     to append to old files. Apart from an input file name, which could have
+     anything in it, the names and values contain no spaces, so the column
+     splits easily, eg with pandas' str.split() and then on '='.
+
+   o The gnuplot script written by write_gnuplot() keeps to features gnuplot
+     has had since version 4.4 - 'plot for' and columnheader() - and uses
+     the plain png terminal rather than pngcairo, which isn't always built
+     in. The working set size is used for the X axis, rather than the
+     array width, so the steps in the times can be read off directly
+     against the cache sizes reported by crsmain --caches.
+
+   o Two runs started within the same second will get the same name from
+     auto_name(), but since the files are appended to rather than
+     overwritten, no results are lost.
+
+*/
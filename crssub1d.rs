@@ -1,4 +1,13 @@
 
+/// Does the same as csub() in crssub.rs, but for arrays held as single
+/// vectors, with ny rows of nx elements stored one after the other.
+///
+/// ```
+/// let input_array = vec![1.0f32; 6];
+/// let mut output_array = vec![0.0f32; 6];
+/// crslib::crssub1d::csub1d(&input_array,3,2,&mut output_array);
+/// assert_eq!(output_array,vec![1.0,2.0,3.0,2.0,3.0,4.0]);
+/// ```
 pub fn csub1d (input_array: &Vec<f32>,nx: usize,ny: usize,
                                       output_array: &mut Vec<f32>) {
     for iy in 0..ny {
@@ -8,6 +17,23 @@ pub fn csub1d (input_array: &Vec<f32>,nx: usize,ny: usize,
     }
 }
 
+//  csub1d_block() does the same as csub1d(), but for a block of ny rows
+//  taken from a larger array, starting at row first_row of that array. The
+//  row number used in the index sum is the row number in the larger array,
+//  so processing an array block by block gives the same result as processing
+//  it all at once. It is used by crsmain_stream.rs.
+
+#[allow(dead_code)]
+pub fn csub1d_block (input_array: &[f32],nx: usize,ny: usize,first_row: usize,
+                                                  output_array: &mut [f32]) {
+    for iy in 0..ny {
+       for ix in 0..nx {
+          output_array[iy * nx + ix] =
+                      input_array[iy * nx + ix] + (ix + iy + first_row) as f32;
+       }
+    }
+}
+
 use std::env;
 fn main() {
    let args: Vec<String> = env::args().collect();
@@ -0,0 +1,70 @@
+//
+//                     c r s s u b 1 d _ c h e c k e d
+//
+// Summary:
+//    Checked version of the flat 1D array access routine in crssub1d.rs.
+//
+// Introduction:
+//    This is part of a study into how well different languages handle accessing
+//    elements of 2D rectangular arrays. csub1d() in crssub1d.rs works on a 2D
+//    array held as a single vector of ny rows of nx elements, and trusts its
+//    caller to pass vectors that are big enough. The routine here checks that
+//    first, and returns an error, using the CsubError type from crserror.rs,
+//    rather than panicking part way through.
+//
+// This version:
+//    This is kept in a file of its own, rather than in crssub1d.rs, because
+//    crssub1d.rs can be built as a program by itself with just 'rustc
+//    crssub1d.rs', and so can't depend on any other file.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::crserror::CsubError;
+use crate::crssub1d::csub1d;
+
+//  csub1d_checked() checks the arrays hold at least nx * ny elements before
+//  calling csub1d(), and returns an error if they don't, or if nx * ny is too
+//  big to be worked out at all.
+
+pub fn csub1d_checked (input_array: &Vec<f32>,nx: usize,ny: usize,
+                         output_array: &mut Vec<f32>) -> Result<(),CsubError> {
+   if nx == 0 || ny == 0 {
+      return Err(CsubError::EmptyArray);
+   }
+   let Some(needed) = nx.checked_mul(ny) else {
+      return Err(CsubError::TooManyElements { nx,ny });
+   };
+   if input_array.len() < needed {
+      return Err(CsubError::BufferTooSmall { array: "input",
+                                          len: input_array.len(),needed });
+   }
+   if output_array.len() < needed {
+      return Err(CsubError::BufferTooSmall { array: "output",
+                                          len: output_array.len(),needed });
+   }
+   csub1d(input_array,nx,ny,output_array);
+   Ok(())
+}
@@ -0,0 +1,66 @@
+//
+//                   c r s s u b 1 d _ c h u n k s . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using row chunks of a 1D array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number. The idea is trivial, but the operation isn't completely
+//    trivial to optimise, and the intention is to see how well this runs when
+//    compiled using different compilers, or using different options.
+//
+// This version:
+//    This version is for Rust, and like crssub1d.rs holds each 2D array as a
+//    single 1D array of Nx * Ny elements in row-major order. It splits the input
+//    and output arrays into rows using chunks_exact() and chunks_exact_mut(),
+//    and works through the rows and the elements of each row using iterators,
+//    in the same way that crssub_iter.rs does for a vector of vectors.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub1d (input_array: &[f32],nx: usize,ny: usize,
+                                                  output_array: &mut [f32]) {
+
+   //  chunks_exact() doesn't allow a chunk size of zero, and with no columns
+   //  there is nothing to do anyway.
+
+   if nx == 0 {
+      return;
+   }
+   let input_rows = input_array[..nx * ny].chunks_exact(nx);
+   let output_rows = output_array[..nx * ny].chunks_exact_mut(nx);
+   for (iy,(input_row,output_row)) in input_rows.zip(output_rows).enumerate() {
+      for (ix,(e,r)) in input_row.iter().zip(output_row.iter_mut()).enumerate() {
+         *r = *e + (ix + iy) as f32;
+      }
+   }
+}
@@ -0,0 +1,104 @@
+//
+//                  c r s s u b 1 d _ c o n v e r t . r s
+//
+// Summary:
+//    2D array access test subroutines in Rust, converting the element type on output.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    The routines here do the same, but the output array has a different element
+//    type from the input array. csub_convert() reads single precision values,
+//    does the addition in single precision, and widens the result to double
+//    precision as it is stored, which is what a program does when it reads
+//    single precision data but wants to accumulate in double. csub_narrow() does
+//    the reverse, adding in double precision and narrowing the result to single
+//    precision. csub_i16() reads 16-bit integers, the form raw detector data
+//    usually comes in, converts each to single precision and does the
+//    addition there. Comparing them with the routines that keep the same type
+//    throughout shows what the conversion costs.
+//
+// This version:
+//    This version is for Rust, and uses a single 1D array for each 2D array,
+//    with the index calculated in the code, just as in crssub1d.rs.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added csub_i16().
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub_convert (input_array: &[f32],nx: usize,ny: usize,
+                                                  output_array: &mut [f64]) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy * nx + ix] = (input_array[iy * nx + ix] + (ix + iy) as f32) as f64;
+      }
+   }
+}
+
+pub fn csub_narrow (input_array: &[f64],nx: usize,ny: usize,
+                                                  output_array: &mut [f32]) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy * nx + ix] = (input_array[iy * nx + ix] + (ix + iy) as f64) as f32;
+      }
+   }
+}
+
+pub fn csub_i16 (input_array: &[i16],nx: usize,ny: usize,
+                                                  output_array: &mut [f32]) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy * nx + ix] = input_array[iy * nx + ix] as f32 + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Converting between f32 and f64 is cheap for the processor. Built with
+     -O -C target-cpu=native on x86_64, rustc 1.95 vectorises both loops,
+     and the conversion becomes a single vcvtps2pd or vcvtpd2ps instruction
+     for every four values, alongside the additions. crsmain1d_convert.rs
+     times them, and the differences it shows are mostly the differences in
+     the number of bytes read and written - see the notes there.
+
+   o In csub_convert(), the addition is done before widening, so the result
+     is exactly what the f32 routines give, just stored in an f64. Widening
+     the input first and adding in f64 would give a slightly different
+     result for values too large to be held exactly in an f32, which is a
+     choice a real program would have to make deliberately.
+
+   o Every i16 value converts to f32 exactly, so csub_i16() gives exactly
+     what csub1d() gives for the same values already held as f32, as long
+     as the sum stays within the 2^24 that an f32 holds exactly. The loop
+     is vectorised too: each group of values is sign extended to 32 bits
+     with vpmovsxwd and converted with vcvtdq2ps, two extra instructions
+     alongside the conversion of the index sum that every version has.
+
+*/
@@ -0,0 +1,84 @@
+//
+//                c r s s u b 1 d _ e n u m e r a t e . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, enumerating the elements of a 1D array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number. The idea is trivial, but the operation isn't completely
+//    trivial to optimise, and the intention is to see how well this runs when
+//    compiled using different compilers, or using different options.
+//
+// This version:
+//    This version is for Rust, and like crssub1d.rs holds each 2D array as a
+//    single 1D array of Nx * Ny elements in row-major order. Rather than two
+//    nested loops, it works through the output array with a single enumerate()
+//    over all its elements, and works out the row and column of each element
+//    from its position in the array, using a division and a remainder.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub1d (input_array: &[f32],nx: usize,ny: usize,
+                                                  output_array: &mut [f32]) {
+
+   //  There is nothing to do for an empty array, and returning here also
+   //  avoids a division by zero when nx is zero.
+
+   if nx == 0 || ny == 0 {
+      return;
+   }
+   output_array[..nx * ny].iter_mut().enumerate().for_each(|(i,o)| {
+      let ix = i % nx;
+      let iy = i / nx;
+      *o = input_array[i] + (ix + iy) as f32;
+   });
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A division and a remainder for every element are expensive - an
+     integer division takes tens of cycles on most processors, while the
+     rest of the work for an element takes one or two - and they also stop
+     the loop being vectorised. crsmain1d_enumerate.rs measures the cost:
+     with rustc 1.95 on an x86_64 laptop, this is about 16 times slower than
+     crssub1d.rs for the default 10 rows of 2000 columns. The compiler does
+     combine the division and the remainder into a single instruction, but
+     it can't avoid it, since it has no way to know that consecutive values
+     of i just step through the columns of successive rows.
+
+   o When nx is a power of two, the division and remainder become a shift
+     and a mask, and the difference is much smaller - but only if the
+     compiler knows nx, which it doesn't here.
+
+*/
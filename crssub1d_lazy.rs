@@ -0,0 +1,72 @@
+//
+//                     c r s s u b 1 d _ l a z y . r s
+//
+// Summary:
+//    2D array access test routine in Rust, returning a lazy iterator over a 1D array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//
+// This version:
+//    This version is for Rust, and, like crssub1d.rs, holds each 2D array as a
+//    single flat 1D array of Nx * Ny elements in row-major order. Unlike the
+//    other versions, it doesn't write to an output array at all. Instead, it
+//    returns an iterator that produces each output value only when it is asked
+//    for, so the caller can collect the values into a vector, add them up, or
+//    pass them on to some further processing, without any output array having
+//    to be allocated unless it is actually wanted.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub_iter (input_array: &[f32],nx: usize,ny: usize)
+                                         -> impl Iterator<Item = f32> + '_ {
+
+   //  Each row of the input array is taken in turn, and each element of that
+   //  row then has the index sum added to it. Nothing is actually done until
+   //  the iterator returned is used. (chunks_exact() doesn't allow a chunk
+   //  size of zero, but if nx is zero there are no elements anyway.)
+
+   input_array[..nx * ny].chunks_exact(nx.max(1)).enumerate()
+      .flat_map(|(iy,row)| {
+         row.iter().enumerate().map(move |(ix,value)| value + (ix + iy) as f32)
+      })
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The iterator borrows the input array, and the '_ in the return type
+     says so; the input array can't be changed while the iterator is in
+     use.
+
+   o Use ./crsmain1d_lazy to compare this with the eager csub1d() in
+     crssub1d.rs.
+
+*/
@@ -0,0 +1,88 @@
+//
+//                   c r s s u b 1 d _ u n s a f e . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using unsafe code on a 1D array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number. The idea is trivial, but the operation isn't completely
+//    trivial to optimise, and the intention is to see how well this runs when
+//    compiled using different compilers, or using different options.
+//
+// This version:
+//    This version is for Rust, and is the flat-array analogue of crssub_unsafe.rs.
+//    Like crssub1d.rs, it holds each 2D array as a single 1D array of Nx * Ny
+//    elements in row-major order, but it accesses the elements using Rust's
+//    get_unchecked() and get_unchecked_mut() methods, so no bounds checking is
+//    performed inside the loops. Instead, it checks once, before the loops
+//    start, that both arrays are large enough for the dimensions passed, and
+//    panics if they are not.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub1d (input_array: &[f32],nx: usize,ny: usize,
+                                                  output_array: &mut [f32]) {
+
+   //  This single check is what makes the unsafe code below safe: every
+   //  index used in the loops is less than nx * ny, so it is within both
+   //  arrays. (checked_mul() guards against nx * ny itself overflowing.)
+
+   let needed = nx.checked_mul(ny).expect("csub1d: nx * ny overflows");
+   assert!(input_array.len() >= needed && output_array.len() >= needed,
+           "csub1d: arrays too small for {} rows of {} columns",ny,nx);
+
+   unsafe {
+      for iy in 0..ny {
+         for ix in 0..nx {
+            *output_array.get_unchecked_mut(iy * nx + ix) =
+                   input_array.get_unchecked(iy * nx + ix) + (ix + iy) as f32;
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o crssub_unsafe.rs performs no checks at all, and relies on the caller
+     passing the right dimensions. With a single flat array there is only
+     one length to check for each array, so it costs essentially nothing
+     to do the check once at the start, and this version can't read or
+     write outside the arrays whatever dimensions it is passed.
+
+   o The arrays are passed as slices rather than as &Vec<f32>, as in
+     crssub1d.rs. A &Vec<f32> can be passed as a slice anyway, and using
+     slices allows crsmain1d_unsafe.rs to pass deliberately undersized
+     arrays to check that the assertion works.
+
+*/
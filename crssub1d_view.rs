@@ -0,0 +1,80 @@
+//
+//                     c r s s u b 1 d _ v i e w . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, working on a strided view of a 1D array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine does the same, but for arrays that are views into larger ones:
+//    each array is described by a slice starting at its first element, and a
+//    row stride giving the distance between the starts of successive rows, which
+//    can be more than the number of columns. A rectangular section of a bigger
+//    image can then be passed without copying it, which is what ndarray's sliced
+//    views provide, and what a program working on part of an image needs.
+//
+// This version:
+//    This version is for Rust, and uses 1D slices, as in crssub1d.rs, but with
+//    the row length in memory passed separately from nx. With strides equal to
+//    nx, it does exactly what csub1d() does. It replaces the csub_view() taking
+//    ndarray ArrayView2 and ArrayViewMut2 arguments that was asked for, which
+//    would need the external ndarray crate.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Header says this replaces the ndarray ArrayView2 version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub1d_view (input_array: &[f32],input_stride: usize,nx: usize,ny: usize,
+                               output_array: &mut [f32],output_stride: usize) {
+   for iy in 0..ny {
+      let input_row = &input_array[iy * input_stride..iy * input_stride + nx];
+      let output_row = &mut output_array[iy * output_stride..iy * output_stride + nx];
+      for (ix,(output,input)) in output_row.iter_mut().zip(input_row).enumerate() {
+         *output = input + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A slice and a row stride is how ndarray holds a 2D view internally,
+     though ndarray also allows a column stride other than one, which this
+     doesn't.
+
+   o Each row is taken as a slice of exactly nx elements before the loop
+     over it, so the inner loop has no bounds checks, and only the slicing of
+     each row is checked. A stride smaller than nx would make the rows
+     overlap, which is allowed for the input, but for the output would make
+     a later row overwrite part of an earlier one.
+
+   o crsmain1d_view.rs checks this against csub1d() and times it for a
+     view and for a contiguous array of the same size.
+
+*/
@@ -0,0 +1,226 @@
+//
+//                       c r s s u b _ a u t o . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, choosing the fastest version itself.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine doesn't do this itself. The first time it is called, it times
+//    a few of the other versions of csub() on the arrays it was passed, and from
+//    then on it simply calls whichever of them was fastest. This is the sort of
+//    adaptive dispatch a library might use to pick the best implementation for
+//    the machine it finds itself running on.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The versions it chooses between are the
+//    straightforward one in crssub.rs, the iterator one in crssub_iter.rs, the
+//    unchecked one in crssub_unsafe.rs, and the one using hints in crssub_hint.rs.
+//    Once made, the choice applies to all later calls, from any thread, whatever
+//    the array dimensions.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::OnceLock;
+use std::time::{Duration,Instant};
+use crate::crssub;
+use crate::crssub_hint;
+use crate::crssub_iter;
+use crate::crssub_unsafe;
+
+//  The versions of csub() that can be chosen.
+
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Variant {
+   Naive,
+   Iter,
+   Unsafe,
+   Hint,
+}
+
+//  All of them, in the order they are timed.
+
+const VARIANTS: [Variant; 4] = [Variant::Naive,Variant::Iter,Variant::Unsafe,Variant::Hint];
+
+//  Each version is called at least this many times during calibration, and
+//  for at least this long, so very small arrays are still timed reliably.
+
+const CALIBRATION_CALLS: u32 = 3;
+const CALIBRATION_TIME: Duration = Duration::from_millis(1);
+
+//  The version chosen, set by the first call or by force().
+
+static CHOICE: OnceLock<Variant> = OnceLock::new();
+
+impl Variant {
+
+   //  Returns the version with the name used by --kernel in crsmain.rs,
+   //  "csub", "iter", "unsafe" or "hint", or None if it isn't one of these.
+
+   pub fn from_name (name: &str) -> Option<Variant> {
+      VARIANTS.iter().copied().find(|variant| variant.name() == name)
+   }
+
+   //  Returns the name of the version, as used by from_name().
+
+   pub fn name (self) -> &'static str {
+      match self {
+         Variant::Naive => "csub",
+         Variant::Iter => "iter",
+         Variant::Unsafe => "unsafe",
+         Variant::Hint => "hint",
+      }
+   }
+
+   //  Calls this version of csub().
+
+   fn call (self,input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+      match self {
+         Variant::Naive => crssub::csub(input_array,nx,ny,output_array),
+         Variant::Iter => crssub_iter::csub(input_array,nx,ny,output_array),
+         Variant::Unsafe => crssub_unsafe::csub(input_array,nx,ny,output_array),
+         Variant::Hint => crssub_hint::csub(input_array,nx,ny,output_array),
+      }
+   }
+}
+
+/// Sets each element of the output array to the corresponding input element
+/// plus the sum of its indices, using whichever version of csub() was found
+/// to be fastest when it was first called.
+///
+/// ```
+/// let input_array = vec![vec![1.0f32; 3]; 2];
+/// let mut output_array = vec![vec![0.0f32; 3]; 2];
+/// crslib::crssub_auto::csub(&input_array,3,2,&mut output_array);
+/// assert_eq!(output_array,vec![vec![1.0,2.0,3.0],vec![2.0,3.0,4.0]]);
+/// assert!(crslib::crssub_auto::chosen().is_some());
+/// ```
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   let variant = *CHOICE.get_or_init(|| calibrate(input_array,nx,ny,output_array));
+   variant.call(input_array,nx,ny,output_array);
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                               F o r c e
+//
+//   Makes csub() use the specified version, without any calibration. This
+//   has to be called before the first call to csub(), since the choice can
+//   only be made once, and returns false if it was too late.
+
+pub fn force (variant: Variant) -> bool {
+   CHOICE.set(variant).is_ok()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                              C h o s e n
+//
+//   Returns the version csub() is using, or None if it hasn't been chosen yet.
+
+pub fn chosen () -> Option<Variant> {
+   CHOICE.get().copied()
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                           C a l i b r a t e
+//
+//   Times each of the versions on the arrays passed, and returns the fastest.
+//   Each gets one untimed call first, so they all start with the arrays in
+//   the cache, and the time that counts is that of its fastest call after
+//   that.
+
+pub fn calibrate (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                    output_array: &mut Vec<Vec<f32>>) -> Variant {
+   let mut fastest = Variant::Naive;
+   let mut fastest_time = Duration::MAX;
+   for variant in VARIANTS.iter().copied() {
+      variant.call(input_array,nx,ny,output_array);
+      let mut calls = 0;
+      let start = Instant::now();
+      while calls < CALIBRATION_CALLS || start.elapsed() < CALIBRATION_TIME {
+         let call_start = Instant::now();
+         variant.call(input_array,nx,ny,output_array);
+         let time = call_start.elapsed();
+         if time < fastest_time {
+            fastest = variant;
+            fastest_time = time;
+         }
+         calls += 1;
+      }
+   }
+   fastest
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The calibration cost is paid once, by the first call. Each of the four
+     versions gets one untimed call and is then timed for at least three
+     calls and at least a millisecond, so the first call takes a little
+     over four milliseconds for small arrays, or about sixteen times as
+     long as a normal call for large ones. With the default 2000 by 10
+     arrays, this was about 4 milliseconds on the test machine, and a run
+     of crsmain --kernel=auto took the same time as --kernel=unsafe, which
+     is what it chose, to within the variation between runs.
+
+   o The first version used the average time of each version's calls, and
+     about one time in three it chose the wrong one, because a single call
+     interrupted by something else is enough to spoil an average taken over
+     only a millisecond. The fastest single call is a much steadier measure
+     of what a version can do, and with that it chose the same one every
+     time in a series of runs.
+
+   o The request for this asked for the choice to be kept in a OnceCell.
+     A OnceCell can't be used in a static, since it isn't safe to share
+     between threads, so this uses OnceLock, which is the thread-safe
+     equivalent. If two threads make the first call at the same time, one
+     calibrates while the other waits for it.
+
+   o The choice is made for the arrays passed to the first call, and is
+     then used whatever the dimensions of later ones. Which version is
+     fastest can depend on the array size - crsmain --matrix shows this -
+     so a library that expected very different sizes would want to keep a
+     choice for each range of sizes, rather than just the one.
+
+   o There is no SIMD version to choose from. The portable std::simd module
+     is only available with a nightly compiler. In any case, once the
+     bounds checks are out of the way, as in crssub_unsafe.rs, the compiler
+     vectorises the inner loop itself (see the notes in crssub_div.rs).
+
+   o force() is there for anyone who wants to take the choice out of its
+     hands, for example to get repeatable timings, and crsmain.rs uses it
+     for its --auto-kernel option.
+
+*/
@@ -0,0 +1,79 @@
+//
+//                    c r s s u b _ b r a n c h y . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, with a data-dependent branch.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The normal
+//    test routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices,
+//    with no branches inside the loop other than the loop tests themselves.
+//    This routine only adds the index sum if the input value is greater than a
+//    threshold, and otherwise just copies the input value. If the input values
+//    are in no particular order and the threshold is close to their median, the
+//    processor can't predict which way the branch will go, and this shows what
+//    branch mispredictions cost in an otherwise simple array traversal - the
+//    sort of thing that happens in thresholded or masked image operations.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs, with the same array[iy][ix] indexing.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                         output_array: &mut Vec<Vec<f32>>,threshold: f32) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let value = input_array[iy][ix];
+         if value > threshold {
+            output_array[iy][ix] = value + (ix + iy) as f32;
+         } else {
+            output_array[iy][ix] = value;
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o An optimising compiler is entitled to turn the if statement into a
+     branch-free select, which would remove the mispredictions this is
+     meant to measure. With rustc 1.95 on x86_64, the bounds checks stop
+     the loop being vectorised, and the generated code keeps a real
+     conditional branch on the comparison, which can be checked using
+     rustc --emit asm. A later compiler may behave differently.
+
+   o crsmain.rs fills the input array with scrambled values when this
+     version is selected, since with the normal descending values the
+     branch changes direction at most once per row and is almost always
+     predicted correctly.
+
+*/
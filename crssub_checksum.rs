@@ -0,0 +1,87 @@
+//
+//                   c r s s u b _ c h e c k s u m . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, returning a checksum.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The difference is that as well as
+//    setting the output array, it adds up all the values it sets and returns
+//    the total, as a double precision checksum.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                               output_array: &mut Vec<Vec<f32>>) -> f64 {
+
+   //  This is the same loop as in crssub.rs, except that each value is also
+   //  added into the checksum. The checksum depends on every value written,
+   //  so the calling code can't be optimised away unless the checksum isn't
+   //  used, and the calling code makes sure that it is.
+
+   let mut checksum = 0.0f64;
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let value = input_array[iy][ix] + (ix + iy) as f32;
+         output_array[iy][ix] = value;
+         checksum += value as f64;
+      }
+   }
+   checksum
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A real worry with a test like this is that the compiler may be able to
+     see that the output array is never used - if, for example, the check
+     of the results is removed - and so can remove the whole loop, giving
+     wonderfully fast and completely meaningless timings. Returning a value
+     that the main routine prints out means the work has to be done. This
+     is the recommended version to use for timing purposes if there is any
+     doubt about what the optimiser may be doing.
+
+   o The checksum does add a little to the work done in the loop. The
+     floating point adds into a single accumulator form a dependency chain
+     that the compiler can't reorder (floating point addition isn't
+     associative), so it can't vectorise the sum, and this version may be
+     slower than crssub.rs for that reason alone. That needs to be borne in
+     mind when comparing the timings.
+
+   o Use ./crsmain --kernel=checksum to run this code.
+
+*/
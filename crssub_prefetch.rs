@@ -0,0 +1,104 @@
+//
+//                   c r s s u b _ p r e f e t c h . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using explicit prefetching.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The difference is that as it works along
+//    each row of the input array it uses the x86_64 _mm_prefetch() intrinsic
+//    to ask for the data a few cache lines ahead of the current position to
+//    be brought into the cache. This means it can only be used on x86_64, and
+//    crsmain.rs and crslib.rs only include it there, and only if they are
+//    built with the 'prefetch' feature, eg:
+//
+//    rustc -O --cfg 'feature="prefetch"' crsmain.rs
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. Only built for x86_64, with the 'prefetch' feature.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::arch::x86_64::{_mm_prefetch,_MM_HINT_T0};
+
+//  The distance ahead of the current element, in elements, at which the
+//  prefetch is requested. A cache line is usually 64 bytes, ie 16 f32 values,
+//  so this is four cache lines ahead.
+
+const PREFETCH_DISTANCE: usize = 64;
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+
+   //  This is the same loop as in crssub.rs, except for the prefetch. The
+   //  address passed to _mm_prefetch() is calculated using wrapping_add(),
+   //  as the pointer will run past the end of the row for the last few
+   //  elements. This is harmless - a prefetch is only a hint, and does not
+   //  fault on an invalid address - but it means the pointer arithmetic must
+   //  not use the normal add(), which requires the result to be in bounds.
+
+   for iy in 0..ny {
+      let input_row = &input_array[iy];
+      for ix in 0..nx {
+         unsafe {
+            _mm_prefetch::<_MM_HINT_T0>(input_row.as_ptr()
+                             .wrapping_add(ix + PREFETCH_DISTANCE) as *const i8);
+         }
+         output_array[iy][ix] = input_row[ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Modern processors have hardware prefetchers that are very good at
+     spotting a simple sequential pass through memory like this one, so
+     explicit prefetching is likely to make no difference at all, or even
+     to make things slightly slower because of the extra instructions in the
+     inner loop. That in itself is an interesting result, and the place to
+     look for any effect at all is with arrays much larger than the cache,
+     eg by using large arrays or the --arrays option of crsmain.rs.
+
+   o This issues a prefetch for every element, which means sixteen
+     prefetches for the same cache line. Only one of them does anything, but
+     doing it this way keeps the inner loop as close as possible to that in
+     crssub.rs. The distance is a const so it can be changed easily, but it
+     needs a recompilation to do so.
+
+   o Use ./crsmain --kernel=prefetch to run this code, in a crsmain built
+     with the 'prefetch' feature.
+
+*/
@@ -0,0 +1,93 @@
+//
+//                     c r s s u b _ p r e f i x . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, with a running sum along each row.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines normally set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices,
+//    and every element can be worked out independently of all the others. In
+//    this routine each element of Out also has the previous element of the same
+//    row of Out added to it, so each row of Out is a running (prefix) sum of
+//    the usual values. The first element of each row has nothing before it, so
+//    is just In plus the row number. Each element now depends on the one before
+//    it, which stops the inner loop being vectorised, but the rows are still
+//    independent of each other.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs, with the same style of indexed loops.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   for iy in 0..ny {
+      if nx > 0 {
+         output_array[iy][0] = input_array[iy][0] + iy as f32;
+      }
+      for ix in 1..nx {
+         output_array[iy][ix] =
+                input_array[iy][ix] + (ix + iy) as f32 + output_array[iy][ix - 1];
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o This is a useful contrast with crssub.rs. There, each element can be
+     worked out independently, so the compiler can do several at once in
+     SIMD registers, and a loop over the row is limited by how fast memory
+     can supply the data. Here each addition needs the result of the one
+     before, so the time per element can be no shorter than the latency of
+     a floating point add, however fast the memory is. Running separate
+     rows in separate threads would still work, as in crssub_threads.rs.
+
+   o crsmain --kernel=prefix checks the result by working out the running
+     sum for each row itself, and times this against crssub.rs. With rustc
+     1.95 and the default 10 rows of 2000 columns, it took about 2.6 times
+     as long; for 1000 by 1000 arrays, about 2.4 times. The result is the
+     same with and without -C target-cpu=native, since there is nothing to
+     vectorise.
+
+   o The code is written the obvious way, reading the previous element back
+     from the output row, and the assembler shows the compiler does exactly
+     that: each vaddss of the previous element reads it from memory, just
+     after it was stored. The compiler can't keep it in a register, because
+     it can't be sure the store didn't change anything else it needs, and so
+     the latency of getting a value back from a store is added to that of
+     the add. A variant keeping the running sum in a local variable, and
+     slicing each row to nx elements first, took just over a quarter of the
+     time this version does for the default size - so most of the 2.6 is
+     due to the way the dependency passes through memory, not the
+     dependency itself.
+
+*/
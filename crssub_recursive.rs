@@ -0,0 +1,151 @@
+//
+//                     c r s s u b _ r e c u r s i v e
+//
+// Summary:
+//    2D array access test subroutine in Rust, dividing the rows up recursively.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine splits the range of rows in half, and then each half in half
+//    again, and so on, until it gets down to a block of no more than BASE_ROWS
+//    rows, which it then works through in the usual way. This is the divide and
+//    conquer approach used by 'cache-oblivious' algorithms, which aim to make
+//    good use of every level of the cache without having to be tuned to any of
+//    them. For comparison, csub_tiled() works through the array in fixed square
+//    tiles of TILE by TILE elements, which is the usual tuned alternative.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The recursion works on slices of the
+//    rows, using split_at() and split_at_mut(), so each half gets its own part
+//    of the output array and no index is ever out of range. The innermost loops
+//    of both routines use iterators over slices of each row, as crssub_iter.rs
+//    does, so the compiler can vectorise them.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  The number of rows below which the recursion stops and the rows are
+//  simply worked through in order.
+
+const BASE_ROWS: usize = 8;
+
+//  The size of the square tiles used by csub_tiled().
+
+const TILE: usize = 64;
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   split(&input_array[..ny],&mut output_array[..ny],nx,0);
+}
+
+//  split() does the rows passed, the first of which is row iy0 of the whole
+//  array, either directly if there are few enough or by splitting them in
+//  two and calling itself for each half.
+
+fn split (in_rows: &[Vec<f32>],out_rows: &mut [Vec<f32>],nx: usize,iy0: usize) {
+   let ny = in_rows.len();
+   if ny <= BASE_ROWS {
+      for (iy,(in_row,out_row)) in in_rows.iter().zip(out_rows.iter_mut()).enumerate() {
+         for (ix,(out,&value)) in out_row[..nx].iter_mut().zip(&in_row[..nx]).enumerate() {
+            *out = value + (ix + iy0 + iy) as f32;
+         }
+      }
+   } else {
+      let half = ny / 2;
+      let (in_top,in_bottom) = in_rows.split_at(half);
+      let (out_top,out_bottom) = out_rows.split_at_mut(half);
+      split(in_top,out_top,nx,iy0);
+      split(in_bottom,out_bottom,nx,iy0 + half);
+   }
+}
+
+//  csub_tiled() does the same as csub(), but a TILE by TILE square of the
+//  array at a time, working along each row of a tile before moving on to
+//  the next. The tiles at the right and bottom edges are cut short to fit.
+
+pub fn csub_tiled (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   for iy0 in (0..ny).step_by(TILE) {
+      let iy1 = (iy0 + TILE).min(ny);
+      for ix0 in (0..nx).step_by(TILE) {
+         let ix1 = (ix0 + TILE).min(nx);
+         for iy in iy0..iy1 {
+            let in_row = &input_array[iy][ix0..ix1];
+            let out_row = &mut output_array[iy][ix0..ix1];
+            for (ix,(out,&value)) in out_row.iter_mut().zip(in_row).enumerate() {
+               *out = value + (ix0 + ix + iy) as f32;
+            }
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o Splitting only the rows, as this does, the elements are still visited
+     in exactly the same order as by crssub_iter.rs: the recursion just
+     decides when each block of rows is started. So there is no cache
+     benefit to be had here, and what crsmain --kernel=recursive measures
+     is the cost of the recursion. With rustc 1.95 and -C target-cpu=native
+     it took between about 0.95 and 1.1 times as long as iter for the
+     default 10 rows of 2000 columns, for 100 by 200, 1000 by 1000, 4000 by
+     4000 and 8000 by 8000 arrays, which is within the run to run variation.
+     For 1000 rows of 10 columns, where each base case only has 80 elements,
+     it was more like 1.1 to 1.2 times as long. With BASE_ROWS set to 1, so
+     the recursion goes right down to single rows, that went up to about
+     1.6, though it made no difference for 1000 by 1000 arrays.
+
+   o For the same reason, tiling doesn't help either, and csub_tiled() came
+     out slower than iter for every size tried, by a lot more than expected:
+     about 1.4 times for 10 by 2000, 1.9 for 100 by 200, 1.4 to 1.6 for 1000
+     rows of 10 (where the tiles are only 10 wide anyway, so it's the extra
+     loop overhead), 3.5 to 4 times for 1000 by 1000 and for 4000 by 4000,
+     and 1.4 to 1.65 times for 8000 by 8000. Changing TILE showed it's the tile height that matters: for 1000
+     by 1000 arrays, tiles of 16 were only 1.14 times slower than iter, 256
+     1.1, and 1024, which is really working along whole rows, 1.02. Working
+     down a tile of 64 rows, each only 256 bytes long, means switching
+     between 128 short streams through memory (64 in each array), which is
+     more than the hardware prefetchers can follow, whereas 16 rows is only
+     32. So for this problem the tuned block size isn't just unnecessary
+     but easy to get badly wrong, which is rather the cache-oblivious case.
+
+   o Tiling and cache-oblivious recursion pay off when elements are used more
+     than once, as in a matrix transpose or multiply, and the array doesn't
+     all fit in the cache; then the blocks that are being reused stay in
+     the cache. Here each element is read once and written once, and the
+     best order is simply the order it is in memory.
+
+   o compare_recursive() in crsmain.rs checks both routines against
+     crssub.rs for the test array, and for every combination of 1, 7, 8, 9,
+     16, 17 and 33 rows with 1, 63, 64, 65 and 130 columns, either side of
+     the point where the recursion stops and of the tile edges.
+
+*/
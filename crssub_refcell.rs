@@ -0,0 +1,102 @@
+//
+//                    c r s s u b _ r e f c e l l . r s
+//
+// Summary:
+//    2D array access test subroutines in Rust, for rows held as Rc<RefCell<Vec<f32>>>.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The routines
+//    here are passed a 2D array (In) with Ny rows and Nx columns, and another
+//    2D array of the same size (Out). They modify Out so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and is deliberately a bad example. Each row of
+//    each array is an Rc<RefCell<Vec<f32>>>, which is what a beginner tends to
+//    end up with once the borrow checker has objected to rows being shared
+//    between different parts of a program. Nothing needs to be passed mutably,
+//    since the rows can be changed through a shared reference, but every access
+//    has to borrow the row through its RefCell, and that borrow is checked, and
+//    recorded, at run time. csub() borrows the input and output rows afresh for
+//    each element, as code written one element at a time naturally does, and
+//    csub_row() borrows each row just once, outside the inner loop.
+//    crsmain_refcell.rs checks both against crssub.rs and times all three.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+//  A row that can be shared, and changed through any of its references.
+
+pub type SharedRow = Rc<RefCell<Vec<f32>>>;
+
+pub fn csub (input_array: &[SharedRow],nx: usize,ny: usize,output_array: &[SharedRow]) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy].borrow_mut()[ix] = input_array[iy].borrow()[ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+pub fn csub_row (input_array: &[SharedRow],nx: usize,ny: usize,output_array: &[SharedRow]) {
+   for iy in 0..ny {
+      let input_row = input_array[iy].borrow();
+      let mut output_row = output_array[iy].borrow_mut();
+      for ix in 0..nx {
+         output_row[ix] = input_row[ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o With rustc 1.95 and -C target-cpu=native, borrowing for every element
+     roughly doubles the time: crsmain_refcell gave ratios of 1.95 to 2.07
+     against the plain &mut Vec version for the default 10 rows of 2000
+     columns, and 1.94 for 1000 by 1000 arrays. Borrowing once per row
+     costs nothing measurable, with ratios between 0.99 and 1.02.
+
+   o The assembler shows why. For each element, csub() checks and updates
+     the borrow count of the input row, checks the output row isn't already
+     borrowed and marks it as borrowed, does the addition, and then puts
+     both counts back, all of them loads and stores to memory. Because the
+     counts are in memory that the compiler can't be sure isn't the data,
+     it also reloads the pointer and length of each row for every element,
+     so nothing can be hoisted out of the inner loop. In csub_row() the
+     borrows are made once outside the loop, and the inner loop is the same
+     scalar loop, with its bounds check, as in crssub.rs.
+
+   o Neither version is vectorised, but then neither is crssub.rs. The
+     lesson is the usual one for RefCell: borrow once, outside the hot
+     loop, and work on the plain slice inside it.
+
+*/
@@ -0,0 +1,95 @@
+//
+//                    c r s s u b _ r o w l o o p . r s
+//
+// Summary:
+//    2D array access test subroutines in Rust, reading each row by iterator or by index.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The routines
+//    here are passed a 2D array (In) with Ny rows and Nx columns, and another
+//    2D array of the same size (Out). They modify Out so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement a
+//    2D array, just as in crssub.rs. There are two routines, identical apart
+//    from the way the inner loop reads the input row. csub_iter() uses
+//    'for (ix,&value) in row.iter().enumerate()', and csub_index() uses
+//    'for ix in 0..row.len()' and then row[ix]. In both, the input row is first
+//    sliced to nx elements, and the output is written by index in the same
+//    way, so any difference in the timings comes from the way the input row is
+//    read. crsmain_rowloop.rs checks and times the two against each other.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub_iter (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   for iy in 0..ny {
+      let row = &input_array[iy][..nx];
+      let output_row = &mut output_array[iy];
+      for (ix,&value) in row.iter().enumerate() {
+         output_row[ix] = value + (ix + iy) as f32;
+      }
+   }
+}
+
+pub fn csub_index (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   for iy in 0..ny {
+      let row = &input_array[iy][..nx];
+      let output_row = &mut output_array[iy];
+      for ix in 0..row.len() {
+         output_row[ix] = row[ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The answer, with rustc 1.95, is that it makes no difference at all. The
+     assembler for the two routines is the same, instruction for
+     instruction, apart from the choice of registers, with and without -C
+     target-cpu=native. In both, there is no bounds check on reading the
+     input row: the iterator doesn't need one, and for row[ix] the compiler
+     can see that ix is always less than row.len(). Timed by
+     crsmain_rowloop, the ratio of the two times varied between about 0.9
+     and 1.03 from run to run for the default array size, and was 1.00 for
+     1000 by 1000 arrays, which is just noise.
+
+   o Neither is vectorised, though. Both still have a bounds check on
+     output_row[ix] inside the inner loop, since nothing tells the compiler
+     how long the output row is, and it even reloads the output row's length
+     and pointer for every element. That bounds check is what matters here,
+     not how the input is read; slicing the output row to nx first, as is
+     done for the input, or zipping the two rows together as crssub_iter.rs
+     does, removes it.
+
+*/
@@ -0,0 +1,103 @@
+//
+//                        c r s s u b _ r o w p t r
+//
+// Summary:
+//    2D array access test subroutine in Rust, using unsafe code and row pointers.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. It is a variation on crssub_unsafe.rs,
+//    which uses get_unchecked() on the outer vector and then on the row for
+//    every element, so as written goes through two levels of indexing each
+//    time. This version does the outer get_unchecked() once for each row, takes
+//    a raw pointer to the start of that row, and then works along the row from
+//    that pointer, so there is only one level of indexing for each element.
+//    The loops and their i32 counters are otherwise as in crssub_unsafe.rs, so
+//    the row pointer is the only difference between them.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  This is as unsafe as crssub_unsafe.rs, and has the same precondition,
+//  which it is up to the caller to meet, as nothing is checked: both
+//  arrays must have at least ny rows, and each of those rows must have at
+//  least nx elements. (nx and ny must also fit in an i32.) If so, every
+//  pointer used is to an element inside a row, and since the input array
+//  is borrowed as shared and the output array as mutable, the two can't
+//  overlap, so the reads and writes can't interfere with each other.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   unsafe {
+      for iy in 0..ny as i32 {
+         let in_row = input_array.get_unchecked(iy as usize).as_ptr();
+         let out_row = output_array.get_unchecked_mut(iy as usize).as_mut_ptr();
+         for ix in 0..nx as i32 {
+            *out_row.add(ix as usize) = *in_row.add(ix as usize) + (ix + iy) as f32;
+         }
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o This turned out to make much more difference than expected. crsmain
+     --kernel=rowptr times it against crssub_unsafe.rs, and built with rustc
+     1.95 and -C target-cpu=native, for the default 10 rows of 2000 columns,
+     it took about 0.23 times as long - over four times faster. --matrix
+     gave 0.12 ns per element for this against 0.58 for the unsafe version,
+     putting it with iter and hint rather than with unsafe. For 100 rows of
+     200 it took about 0.58 of the unsafe time, for 21 rows of 13 about 0.6,
+     and even for 1000 by 1000 arrays, which don't fit in the level 2 cache,
+     between 0.7 and 0.8. For 4000 by 4000 arrays, where both are waiting on
+     memory, about 0.6.
+
+   o The assembler shows why. The unsafe version isn't vectorised at all: in
+     its inner loop the compiler reloads the pointer to the data of the
+     input row, and of the output row, from the row's Vec for every element.
+     It has to, because as far as it knows a store to an f32 in the output
+     row could change the Vec that holds the pointer to the input row. (The
+     outer &Vec and &mut Vec are known not to overlap, but the row buffers
+     they point to are just memory on the heap.) With the two pointers
+     taken once for the row, the loop is an ordinary loop over two
+     pointers, and is vectorised just as the iterator versions are. So the
+     two levels of indexing didn't cost much in themselves; what they cost
+     was the vectorisation.
+
+   o The precondition is documented at the top of csub(). Like csub() in
+     crssub_unsafe.rs this is a safe function, so the compiler can't make
+     the caller meet it, and a short row gives undefined behaviour rather
+     than a panic. A function marked unsafe would be more honest, but then
+     it couldn't be passed to run() in crsmain.rs like the other versions.
+
+*/
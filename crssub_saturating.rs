@@ -0,0 +1,80 @@
+//
+//                 c r s s u b _ s a t u r a t i n g . r s
+//
+// Summary:
+//    2D array access test subroutines in Rust, for saturating integer arrays.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    For integer arrays, that addition can overflow. For something like an
+//    image of photon counts, the right thing to do then is usually to saturate
+//    - to stop at the largest value the type can hold - rather than to wrap
+//    round to a large negative value.
+//
+// This version:
+//    This version is for Rust, uses vectors of 1D vectors of i32 values to
+//    implement a 2D array, and provides two versions of csub(). One uses
+//    i32::saturating_add() for the addition, and the other uses
+//    i32::wrapping_add(), which wraps on overflow just as the hardware addition
+//    does, and so shows what the saturation checks cost.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub_saturating (input_array: &Vec<Vec<i32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<i32>>) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] = input_array[iy][ix].saturating_add((ix + iy) as i32);
+      }
+   }
+}
+
+pub fn csub_wrapping (input_array: &Vec<Vec<i32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<i32>>) {
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] = input_array[iy][ix].wrapping_add((ix + iy) as i32);
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o x86_64 has no scalar saturating add for 32-bit integers, so the scalar
+     saturating version needs a compare and a conditional move as well as
+     the add. With AVX-512 there are still no packed 32-bit saturating adds
+     (there are for 8 and 16-bit values), but the compiler can vectorise the
+     overflow check, so how much this costs depends a lot on whether the
+     loop gets vectorised at all.
+
+   o Use ./crsmain --dtype=i32 --saturate to compare the two.
+
+*/
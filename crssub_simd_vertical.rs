@@ -0,0 +1,191 @@
+//
+//              c r s s u b _ s i m d _ v e r t i c a l . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, working on eight rows at once.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    When the compiler vectorises the usual loop, each SIMD operation works on
+//    several neighbouring elements of the same row - horizontal vectorisation.
+//    This routine is organised the other way: it takes the rows eight at a
+//    time, and for each column gathers the element from each of the eight rows
+//    into a group of eight lanes, adds each lane's row index plus the column
+//    index, and scatters the results back to the eight output rows. This is
+//    vertical vectorisation, and since the eight elements are a whole row apart
+//    in memory, every load and store is to a different place.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. Stable Rust has no portable SIMD types,
+//    so on x86_64 processors with AVX2 this uses the std::arch intrinsics for
+//    256-bit registers directly, and elsewhere it falls back on an [f32; 8]
+//    array for the lanes. Any rows left over once the rows have been taken in
+//    eights are done one at a time, in the usual way.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::convert::TryInto;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+//  The number of rows worked on together, one per lane.
+
+const LANES: usize = 8;
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+   let nblocks = ny / LANES;
+   let blocks = input_array[..nblocks * LANES].chunks_exact(LANES)
+                           .zip(output_array[..nblocks * LANES].chunks_exact_mut(LANES));
+   for (iblock,(in_rows,out_rows)) in blocks.enumerate() {
+
+      //  Each row is sliced to exactly nx elements, so every ix in the loops
+      //  below is known to be within all of them. each_mut() gives separate
+      //  mutable references to the eight output rows at once.
+
+      let in_rows: &[Vec<f32>; LANES] = in_rows.try_into().unwrap();
+      let out_rows: &mut [Vec<f32>; LANES] = out_rows.try_into().unwrap();
+      let in_rows = in_rows.each_ref().map(|row| &row[..nx]);
+      let mut out_rows = out_rows.each_mut().map(|row| &mut row[..nx]);
+      let iy0 = iblock * LANES;
+
+      #[cfg(target_arch = "x86_64")]
+      {
+         if is_x86_feature_detected!("avx2") {
+            unsafe { block_avx2(&in_rows,&mut out_rows,nx,iy0); }
+            continue;
+         }
+      }
+      block_lanes(&in_rows,&mut out_rows,nx,iy0);
+   }
+   for iy in nblocks * LANES..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] = input_array[iy][ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+//  uses_intrinsics() returns true if csub() will use the AVX2 intrinsics on
+//  this processor, rather than the portable version.
+
+pub fn uses_intrinsics () -> bool {
+   #[cfg(target_arch = "x86_64")]
+   {
+      if is_x86_feature_detected!("avx2") {
+         return true;
+      }
+   }
+   false
+}
+
+//  block_avx2() does one block of eight rows using AVX2 intrinsics, with one
+//  row in each lane of a 256-bit register. _mm256_setr_ps() gathers the
+//  element for the current column from each row, the index sums are worked
+//  out as integers and converted, as (ix + iy) as f32 would be, and the
+//  results are stored to a small array and scattered to the output rows.
+//  It must only be called if the processor supports AVX2.
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn block_avx2 (in_rows: &[&[f32]; LANES],out_rows: &mut [&mut [f32]; LANES],
+                                                          nx: usize,iy0: usize) {
+   let rows = _mm256_add_epi32(_mm256_set1_epi32(iy0 as i32),
+                               _mm256_setr_epi32(0,1,2,3,4,5,6,7));
+   let mut lanes = [0.0f32; LANES];
+   for ix in 0..nx {
+      let values = _mm256_setr_ps(in_rows[0][ix],in_rows[1][ix],in_rows[2][ix],
+                                  in_rows[3][ix],in_rows[4][ix],in_rows[5][ix],
+                                  in_rows[6][ix],in_rows[7][ix]);
+      let sums = _mm256_cvtepi32_ps(_mm256_add_epi32(rows,_mm256_set1_epi32(ix as i32)));
+      _mm256_storeu_ps(lanes.as_mut_ptr(),_mm256_add_ps(values,sums));
+      for lane in 0..LANES {
+         out_rows[lane][ix] = lanes[lane];
+      }
+   }
+}
+
+//  block_lanes() does the same using an [f32; 8] array for the lanes, and is
+//  used on other processors. See the notes for what the compiler makes of it.
+
+fn block_lanes (in_rows: &[&[f32]; LANES],out_rows: &mut [&mut [f32]; LANES],
+                                                          nx: usize,iy0: usize) {
+   for ix in 0..nx {
+      let mut lanes = [0.0f32; LANES];
+      for lane in 0..LANES {
+         lanes[lane] = in_rows[lane][ix] + (ix + iy0 + lane) as f32;
+      }
+      for lane in 0..LANES {
+         out_rows[lane][ix] = lanes[lane];
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The first version of this used only the [f32; 8] lanes, in the hope
+     that the compiler would keep them in one 256-bit register. With rustc
+     1.95 it didn't: the assembler showed the lane loops turned back into
+     ordinary vector code going along each row, eight columns at a time from
+     one row, which is exactly what this routine is meant not to do. That's
+     why the x86_64 version uses the intrinsics, and checking with rustc
+     --emit asm shows the vinsertps instructions gathering one element from
+     each row and then a single vaddps for all eight. The portable version
+     is kept for other processors, but there it is really a horizontal
+     version written in an odd way.
+
+   o The intrinsics version is chosen at run time, using
+     is_x86_feature_detected!(), so it gets used even without -C
+     target-cpu=native. The check is made once per block of eight rows, which
+     is cheap compared to the work in each block.
+
+   o crsmain --kernel=vertical times this against crssub.rs and
+     crssub_unsafe.rs. Built with -C target-cpu=native, for the default 10
+     rows of 2000 columns, it took about 1.25 times as long as the unsafe
+     version, and about 0.7 times as long as crssub.rs, whose bounds checks
+     cost more than the gather and scatter do. For 1000 by 1000 arrays and
+     4000 by 4000 arrays it was about 1.13 times the unsafe time, the
+     difference shrinking as both become limited by memory rather than by
+     the instructions. For small arrays, such as 13 by 21, it was nearly 1.5
+     times slower. So the memory system does prefer the horizontal layout,
+     but by less than might be expected: each of the eight rows is read and
+     written in order, so the prefetchers still see eight simple streams,
+     and it is the extra instructions for the gather and scatter, rather
+     than the memory access, that cost the most.
+
+   o The index sums are worked out as integers and then converted, rather
+     than by keeping a vector of float sums and adding 1.0 each time, so the
+     results match (ix + iy) as f32 exactly even for sums too big to be held
+     exactly in an f32. That needs AVX2 for the integer add; AVX alone would
+     have been enough for the floating point.
+
+*/
@@ -0,0 +1,76 @@
+//
+//                       c r s s u b _ s t r i d e d
+//
+// Summary:
+//    2D array access test subroutine in Rust, touching every Nth element of each row.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. The test
+//    routines set each element of an output array to the value of the
+//    corresponding element of an input array plus the sum of its two indices.
+//    This routine only does this for every stride'th element along each row,
+//    starting with the first, and leaves the rest of the output untouched. With
+//    a stride of 1 it is the same as crssub.rs. As the stride goes up, less of
+//    each cache line that is brought in gets used, until from a stride of 16
+//    (64 bytes of f32 values) each element touched is in a cache line of its
+//    own, and then the hardware prefetchers have to follow ever bigger jumps.
+//    Timing it for a range of strides, as crsmain_strided.rs does, gives the
+//    classic curve of time per element against stride.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs, and the same indexing, with step_by()
+//    giving the columns to use.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//  The stride must be at least 1. Elements in columns that aren't a multiple
+//  of the stride are left as they were.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                             output_array: &mut Vec<Vec<f32>>,stride: usize) {
+   for iy in 0..ny {
+      for ix in (0..nx).step_by(stride) {
+         output_array[iy][ix] = input_array[iy][ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+//  touched() returns the number of elements in each row of nx elements that
+//  csub() sets for a given stride.
+
+pub fn touched (nx: usize,stride: usize) -> usize {
+   nx.div_ceil(stride)
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o The results are in the notes at the end of crsmain_strided.rs.
+
+*/
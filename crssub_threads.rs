@@ -0,0 +1,162 @@
+//
+//                    c r s s u b _ t h r e a d s . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using multiple threads.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The difference is that the rows of the
+//    array are divided up into contiguous blocks, and each block is processed
+//    by a separate thread. This uses the scoped threads in the standard
+//    library, so no external crates are needed.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//    14th Oct 2026. A single thread now runs on the calling thread.
+//    14th Oct 2026. default_threads() moved here from crsmain.rs.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::io;
+use std::num::NonZeroUsize;
+use std::thread;
+
+//  ----------------------------------------------------------------------------
+//
+//                        R o w s  P e r  T h r e a d
+//
+//   Returns the number of rows handled by each thread, given the number of
+//   rows and the number of threads. The last thread may get fewer. This is
+//   public so that code setting up the arrays can divide them up the same
+//   way.
+
+pub fn rows_per_thread (ny: usize,nthreads: usize) -> usize {
+   let nthreads = nthreads.max(1);
+   ny.div_ceil(nthreads).max(1)
+}
+
+//  ----------------------------------------------------------------------------
+//
+//                         D e f a u l t  T h r e a d s
+//
+/// Returns the number of threads to use if none was specified, given the
+/// result of asking how many processors are available. This can fail, for
+/// example in a container with restricted access to the system's details,
+/// and if so 1 thread is used, with a warning if warn is set - ie if the
+/// thread count is actually going to be used. The result is passed in,
+/// rather than got here, so a failure can be simulated:
+///
+/// ```
+/// use std::io;
+/// use std::num::NonZeroUsize;
+/// use crslib::crssub_threads::default_threads;
+/// assert_eq!(default_threads(Err(io::Error::other("simulated")),true),1);
+/// assert_eq!(default_threads(Err(io::Error::other("simulated")),false),1);
+/// assert_eq!(default_threads(Ok(NonZeroUsize::new(6).unwrap()),true),6);
+/// ```
+pub fn default_threads (parallelism: io::Result<NonZeroUsize>,warn: bool) -> usize {
+   match parallelism {
+      Ok(count) => count.get(),
+      Err(error) => {
+         if warn {
+            println! ("Unable to get the number of processors ({}), using 1 thread",
+                                                                         error);
+         }
+         1
+      }
+   }
+}
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                     output_array: &mut Vec<Vec<f32>>,nthreads: usize) {
+
+   //  The input and output rows are split into matching blocks using
+   //  chunks() and chunks_mut(), and each pair of blocks is handed to its
+   //  own thread. Because chunks_mut() gives each thread its own mutable
+   //  slice of the output rows, the compiler can see that the threads can't
+   //  interfere with one another. The scope waits for all the threads to
+   //  finish before returning.
+
+   //  With only one thread, the rows are processed on the calling thread,
+   //  without starting a new one, so this is the same as the serial code
+   //  and gives a fair baseline for measuring the speedup with more threads.
+
+   if nthreads <= 1 {
+      for iy in 0..ny {
+         for ix in 0..nx {
+            output_array[iy][ix] = input_array[iy][ix] + (ix + iy) as f32;
+         }
+      }
+      return;
+   }
+
+   let block = rows_per_thread(ny,nthreads);
+   thread::scope(|scope| {
+      for (ithread,(input_rows,output_rows)) in input_array[..ny].chunks(block)
+                    .zip(output_array[..ny].chunks_mut(block)).enumerate() {
+         let first_row = ithread * block;
+         scope.spawn(move || {
+            for (irow,(input_row,output_row)) in
+                       input_rows.iter().zip(output_rows.iter_mut()).enumerate() {
+               let iy = first_row + irow;
+               for ix in 0..nx {
+                  output_row[ix] = input_row[ix] + (ix + iy) as f32;
+               }
+            }
+         });
+      }
+   });
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o A new set of threads is started for each call. Starting a thread takes
+     some tens of microseconds, which is comparable to the time taken to
+     process the whole of an array of the default size, so this will only
+     show any benefit for large arrays. A version that kept a pool of threads
+     waiting for work would do better for small arrays, but would be a lot
+     more complicated.
+
+   o Use ./crsmain --kernel=threads --threads=N to run this code, and add
+     --scaling to time it for a range of thread counts.
+
+   o The natural way to do this in Rust would be to use the rayon crate,
+     with a thread pool built by rayon::ThreadPoolBuilder and sized from
+     --threads, and the rows processed using par_iter_mut(). These programs
+     are built using plain rustc, with no access to external crates, so
+     this uses std::thread::scope() instead, which gives the same control
+     over the number of threads but without the pool.
+
+*/
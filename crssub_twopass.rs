@@ -0,0 +1,83 @@
+//
+//                    c r s s u b _ t w o p a s s . r s
+//
+// Summary:
+//    2D array access test subroutine in Rust, using two passes over the output.
+//
+// Introduction:
+//    This is a test routine written as part of a study into how well different
+//    languages handle accessing elements of 2D rectangular arrays. This routine
+//    is passed a 2D array (In) with Ny rows and Nx columns, and another 2D
+//    array of the same size (Out). It modifies Out so so each element of Out
+//    is set to the value of the corresponding element of In, plus the sum of
+//    the two index values for the element - ie plus the row number and the
+//    column number.
+//
+// This version:
+//    This version is for Rust, and uses vectors of 1D vectors to implement
+//    a 2D array, just as in crssub.rs. The difference is that it works in two
+//    passes: first it clears the output array to zero, using fill(), and then
+//    it goes through the array again, adding the input value and the index sum
+//    to each element. This is the way code often ends up being written when
+//    the initialisation and the update are done in different places, and it
+//    means the output array is touched twice.
+//
+// Author(s): agent, agent@local
+//
+// History:
+//    14th Oct 2026. Original version.
+//
+// Copyright (c) 2026 agent
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
+                                      output_array: &mut Vec<Vec<f32>>) {
+
+   //  The first pass clears the output rows, the second does the same work
+   //  as the loop in crssub.rs, but adds to the cleared values rather than
+   //  just setting them.
+
+   for output_row in output_array.iter_mut().take(ny) {
+      output_row[..nx].fill(0.0);
+   }
+   for iy in 0..ny {
+      for ix in 0..nx {
+         output_array[iy][ix] += input_array[iy][ix] + (ix + iy) as f32;
+      }
+   }
+}
+
+/*  ----------------------------------------------------------------------------
+
+                  P r o g r a m m i n g   N o t e s
+
+   o For arrays that fit in the cache, the first pass costs relatively
+     little. Once the arrays are larger than the cache, each pass has to
+     bring the whole of the output array in from memory and write it back,
+     so the memory traffic for the output array doubles, and since this is
+     a memory bound operation, the time taken should rise to match. It's a
+     simple demonstration of why a single fused pass over the data matters.
+
+   o The compiler could, in principle, spot that the cleared values are
+     immediately overwritten and merge the two loops. It doesn't seem to.
+
+   o Use ./crsmain --kernel=twopass to run this code.
+
+*/
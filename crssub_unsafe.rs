@@ -25,10 +25,12 @@
 //    more efficiently.
 //
 // Author(s): Keith Shortridge, Keith@KnaveAndVarlet.com.au
+//            agent, agent@local (the changes dated 14th Oct 2026)
 //
 // History:
 //    28th Oct 2019. Original version. KS (based on code supplied by
 //                   Francois-Xavier Pineau).
+//    14th Oct 2026. Doc examples check it against crssub.rs.
 //
 // Copyright (c) 2019 Knave and Varlet
 //
@@ -50,6 +52,39 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+/// Sets each element of output_array to the corresponding element of
+/// input_array plus the sum of its two indices, for the first ny rows and
+/// nx columns of the arrays, without any bounds checks. Both arrays must
+/// have at least ny rows of at least nx elements, and nothing here checks
+/// that they do.
+///
+/// Only the elements inside those dimensions are touched:
+///
+/// ```
+/// let input_array = vec![vec![1.0f32; 4]; 3];
+/// let mut output_array = vec![vec![0.0f32; 4]; 3];
+/// crslib::crssub_unsafe::csub(&input_array,1,1,&mut output_array);
+/// assert_eq!(output_array[0],vec![1.0,0.0,0.0,0.0]);
+/// assert_eq!(output_array[1],vec![0.0; 4]);
+/// ```
+///
+/// The results are exactly those of crssub.rs, for a single element, a
+/// single row or column and some awkward shapes. Every row is exactly nx
+/// long, the boundary case for the unchecked accesses, and the two outputs
+/// start out different, so an element either version missed would show:
+///
+/// ```
+/// let shapes = [(1,1),(2,1),(1,2),(1,1000),(1000,1),(3,5),(5,3),(7,13),(17,1)];
+/// for &(nx,ny) in shapes.iter() {
+///    let input_array: Vec<Vec<f32>> = (0..ny).map(|iy|
+///          (0..nx).map(|ix| (nx - ix + ny - iy) as f32).collect()).collect();
+///    let mut checked = vec![vec![-1.0f32; nx]; ny];
+///    let mut unchecked = vec![vec![-2.0f32; nx]; ny];
+///    crslib::crssub::csub(&input_array,nx,ny,&mut checked);
+///    crslib::crssub_unsafe::csub(&input_array,nx,ny,&mut unchecked);
+///    assert_eq!(unchecked,checked,"{} rows of {} columns",ny,nx);
+/// }
+/// ```
 pub fn csub (input_array: &Vec<Vec<f32>>,nx: usize,ny: usize,
                                       output_array: &mut Vec<Vec<f32>>) {
 